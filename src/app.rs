@@ -1,17 +1,77 @@
 use leptos::{
-    component, create_signal, event_target_value, store_value, view, IntoView, ReadSignal,
-    Show, SignalGet, SignalSet, SignalUpdate, WriteSignal,
+    component, create_effect, create_memo, create_signal, event_target_value, store_value, view, CollectView,
+    IntoView, Memo, ReadSignal, Show, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWith, WriteSignal,
 };
 
-use crate::challenge::{format_number, generate_challenges, get_daily_seed, Challenge};
-use crate::parser::parse_answer;
-use crate::scoring::{evaluate, ScoreResult};
+use crate::challenge::{
+    format_number, format_scientific, generate_challenges, get_daily_seed, local_day, today, Challenge, Operation,
+};
+use crate::diagnosis::diagnose;
+use crate::history::{self, History, ProblemRecord, SessionRecord};
+use crate::parser::{parse_answer, Answer, AnswerMode, DecimalSeparator, ParseError, ParseOptions, SuffixStyle};
+use crate::rating::{Rating, RatingState};
+use crate::scoring::{
+    hit_rate, log_error, Attempt, Components, Estimate, RuleKind, Score, ScoreResult, BANDS, INTERVAL_COVERAGE,
+};
+use crate::session::{attempt, ProblemResult, Session};
+use crate::stats::{
+    accuracy_by_operation, accuracy_by_skill, bias_by_operation, daily, past_problems, rolling_mean, value_range,
+    Accuracy, DayStats, Skill, ROLLING_WINDOW,
+};
+use crate::storage::{browser_storage, load, save, StorageError};
 
 const PROBLEMS_PER_DAY: usize = 5;
+const RATING_KEY: &str = "oom-trainer-rating";
+const SESSION_KEY: &str = "oom-trainer-session";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InputMode {
+    Buttons,
+    Text,
+    Exponent,
+    Interval,
+}
+
+impl InputMode {
+    fn answer_mode(self) -> AnswerMode {
+        if self == Self::Exponent { AnswerMode::Exponent } else { AnswerMode::Value }
+    }
+}
+
+fn format_seconds(seconds: f64) -> String {
+    if seconds < 60.0 {
+        format!("{seconds:.1}s")
+    } else {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let whole = seconds.round() as u64;
+        format!("{}m {:02}s", whole / 60, whole % 60)
+    }
+}
+
+/// Half-widths in OOM offered for point answers in interval mode
+const INTERVAL_WIDTHS: [f64; 4] = [0.1, 0.25, 0.5, 1.0];
+
+/// Turn a point into the interval `point ÷ 10^half_width ..= point × 10^half_width`;
+/// ranges are kept as given.
+fn widen(answer: Answer, half_width: f64) -> Answer {
+    let Answer::Point(point) = answer else {
+        return answer;
+    };
+    let factor = 10_f64.powf(half_width);
+    match (Estimate::new(point.get() / factor), Estimate::new(point.get() * factor)) {
+        (Ok(low), Ok(high)) if point.is_negative() => Answer::Range { low: high, high: low },
+        (Ok(low), Ok(high)) => Answer::Range { low, high },
+        _ => answer,
+    }
+}
 
 const MAGNITUDES: &[(f64, &str)] = &[(1e3, "K"), (1e6, "M"), (1e9, "B"), (1e12, "T")];
 
 fn format_answer_display(value: f64) -> String {
+    if value <= -1.0 {
+        return format!("-{}", format_answer_display(-value));
+    }
     if value < 1.0 {
         return "0".to_string();
     }
@@ -29,16 +89,170 @@ fn format_answer_display(value: f64) -> String {
     format!("{value:.0}")
 }
 
-fn get_direction_indicator(user: f64, correct: f64) -> (&'static str, &'static str) {
-    if user > correct * 1.05 {
+fn browser_decimal_separator() -> DecimalSeparator {
+    web_sys::window()
+        .and_then(|w| w.navigator().language())
+        .map_or_else(DecimalSeparator::default, |tag| DecimalSeparator::for_language(&tag))
+}
+
+fn format_answer(answer: Answer) -> String {
+    match answer {
+        Answer::Point(value) => format_number(value.get()),
+        Answer::Range { low, high } => format!("{} – {}", format_number(low.get()), format_number(high.get())),
+    }
+}
+
+/// Live interpretation shown under the text field, e.g. "= 4.0 × 10¹¹ (400.0 billion)".
+fn format_preview(answer: Answer) -> String {
+    match answer {
+        Answer::Point(value) => format!("= {} ({})", format_scientific(value.get()), format_number(value.get())),
+        Answer::Range { low, high } => format!(
+            "= {} – {} ({})",
+            format_scientific(low.get()),
+            format_scientific(high.get()),
+            format_answer(answer)
+        ),
+    }
+}
+
+/// Compares magnitudes, so "Too high" on a negative answer means too far from zero.
+fn get_direction_indicator(log_error: f64) -> (&'static str, &'static str) {
+    // About 5% either way
+    const TOLERANCE: f64 = 0.02;
+    if log_error > TOLERANCE {
         ("Too high", "high")
-    } else if user < correct * 0.95 {
+    } else if log_error < -TOLERANCE {
         ("Too low", "low")
     } else {
         ("", "")
     }
 }
 
+/// e.g. "right" or "2 too high (check the zero count)".
+fn describe_exponent(components: Components) -> String {
+    let off = components.exponent_error;
+    match off {
+        0 => "right".to_string(),
+        _ => {
+            let direction = if off > 0 { "high" } else { "low" };
+            format!("{} too {direction} (check the zero count)", off.abs())
+        }
+    }
+}
+
+/// Where a distance fell among the bands, e.g. "Close band (0.1–0.5 OOM),
+/// 0.24 OOM short of Exact".
+fn describe_band(distance: f64) -> String {
+    let mut inner = 0.0;
+    let mut better = None;
+    for (result, edge) in BANDS {
+        if distance <= edge {
+            let band = format!("{} band ({inner}–{edge} OOM)", result.label().trim_end_matches('!'));
+            return match better {
+                Some((label, edge)) => format!("{band}, {:.2} OOM short of {label}", distance - edge),
+                None => band,
+            };
+        }
+        better = Some((result.label().trim_end_matches('!'), edge));
+        inner = edge;
+    }
+    format!("outside every band, {:.2} OOM short of Partial", distance - inner)
+}
+
+/// The distance line of the result card. A wrong sign lands outside every
+/// band however close the magnitude is, so it says that instead.
+fn describe_distance(distance: f64, sign_wrong: bool) -> String {
+    if sign_wrong {
+        format!("Magnitude off by {distance:.2} orders of magnitude, but the sign is wrong, so no band applies")
+    } else {
+        format!("Off by {distance:.2} orders of magnitude: {}", describe_band(distance))
+    }
+}
+
+/// Inline log-scale number line centred on the truth, showing the band edges
+/// and the guess (a segment for a range).
+/// Widest the number line gets each side of the truth, in OOM; guesses
+/// further out are pinned to the edge
+const NUMBER_LINE_MAX_SPAN: f64 = 3.0;
+
+/// How far each side of the truth the number line reaches.
+fn number_line_span(low_error: f64, high_error: f64) -> f64 {
+    (low_error.abs().max(high_error.abs()) + 0.25).clamp(1.5, NUMBER_LINE_MAX_SPAN)
+}
+
+/// The guess's label: "you", or with its distance when pinned to the edge.
+fn number_line_label(estimate_error: f64, span: f64) -> String {
+    if estimate_error.abs() > span {
+        let arrow = if estimate_error > 0.0 { "→" } else { "←" };
+        format!("you {estimate_error:+.0} {arrow}")
+    } else {
+        "you".to_string()
+    }
+}
+
+#[component]
+fn NumberLine(low_error: f64, high_error: f64, estimate_error: f64) -> impl IntoView {
+    const WIDTH: f64 = 300.0;
+    const MARGIN: f64 = 12.0;
+    let (low_error, high_error) = (low_error.min(high_error), low_error.max(high_error));
+    let span = number_line_span(low_error, high_error);
+    let x = move |error: f64| WIDTH / 2.0 + error.clamp(-span, span) / span * (WIDTH / 2.0 - MARGIN);
+    #[allow(clippy::cast_possible_truncation)]
+    let max_tick = span.floor() as i32;
+    // Keep an edge label inside the view
+    let label_anchor = match estimate_error {
+        e if e > span => "end",
+        e if e < -span => "start",
+        _ => "middle",
+    };
+    view! {
+        <svg class="number-line" viewBox="0 0 300 64" role="img" aria-label="Guess and truth on a log scale">
+            {BANDS.iter().rev().map(|&(result, edge)| view! {
+                <rect
+                    class=format!("band band-{}", result.label().trim_end_matches('!').to_lowercase())
+                    x=x(-edge) y="14" width=x(edge) - x(-edge) height="20"
+                />
+            }).collect_view()}
+            <line class="axis" x1=MARGIN y1="34" x2=WIDTH - MARGIN y2="34" />
+            {(-max_tick..=max_tick).map(|k| view! {
+                <line class="tick" x1=x(f64::from(k)) y1="34" x2=x(f64::from(k)) y2="39" />
+                <text class="tick-label" x=x(f64::from(k)) y="50" text-anchor="middle">
+                    {if k == 0 { "truth".to_string() } else { format!("{k:+}") }}
+                </text>
+            }).collect_view()}
+            <line class="truth" x1=x(0.0) y1="10" x2=x(0.0) y2="38" />
+            {(high_error > low_error).then(|| view! {
+                <line class="guess-range" x1=x(low_error) y1="24" x2=x(high_error) y2="24" />
+            })}
+            <circle class="guess" cx=x(estimate_error) cy="24" r="5" />
+            <text class="tick-label" x=x(estimate_error) y="9" text-anchor=label_anchor>
+                {number_line_label(estimate_error, span)}
+            </text>
+        </svg>
+    }
+}
+
+/// A mantissa within this many percent counts as right
+const MANTISSA_TOLERANCE: f64 = 5.0;
+
+/// e.g. "within 3%" or "35% too low".
+fn describe_mantissa(components: Components) -> String {
+    let percent = components.mantissa_percent();
+    if percent.abs() < MANTISSA_TOLERANCE {
+        format!("within {:.0}%", percent.abs().max(1.0))
+    } else {
+        let direction = if percent > 0.0 { "high" } else { "low" };
+        format!("{:.0}% too {direction}", percent.abs())
+    }
+}
+
+/// Compiler-style marker line: spaces up to the error, then carets under it.
+fn caret_line(input: &str, error: &ParseError) -> String {
+    let start = input.get(..error.span.start).map_or(0, |s| s.chars().count());
+    let width = input.get(error.span.clone()).map_or(0, |s| s.chars().count()).max(1);
+    format!("{}{}", " ".repeat(start), "^".repeat(width))
+}
+
 fn progress_percent(current: usize, total: usize) -> f64 {
     #[allow(clippy::cast_precision_loss)]
     let percent = (current as f64 / total as f64) * 100.0;
@@ -63,6 +277,11 @@ fn ProgressBar(current: ReadSignal<usize>, total_score: Box<dyn Fn() -> u32>) ->
     }
 }
 
+/// A negative right-hand operand is bracketed so "x -47" reads unambiguously.
+fn format_operand(n: f64) -> String {
+    if n < 0.0 { format!("({})", format_number(n)) } else { format_number(n) }
+}
+
 #[component]
 fn ProblemCard(challenge: Challenge) -> impl IntoView {
     let operator = if challenge.is_division { "/" } else { "x" };
@@ -72,7 +291,7 @@ fn ProblemCard(challenge: Challenge) -> impl IntoView {
             <div class="problem">
                 <span class="num">{format_number(challenge.num1)}</span>
                 <span class="operator">{operator}</span>
-                <span class="num">{format_number(challenge.num2)}</span>
+                <span class="num">{format_operand(challenge.num2)}</span>
                 <span class="operator">"="</span>
                 <span class="question">"?"</span>
             </div>
@@ -82,28 +301,49 @@ fn ProblemCard(challenge: Challenge) -> impl IntoView {
 
 #[component]
 fn ResultCard(
-    result: ScoreResult,
-    user_answer: f64,
-    correct: f64,
+    score: Score,
+    user_answer: Answer,
+    challenge: Challenge,
+    seconds: f64,
     current_index: ReadSignal<usize>,
     on_next: Box<dyn Fn()>,
 ) -> impl IntoView {
+    let result = score.result;
     let result_class = match result {
         ScoreResult::Exact | ScoreResult::Close => "result-card correct",
-        ScoreResult::Partial => "result-card close",
+        ScoreResult::Partial | ScoreResult::WrongSign => "result-card close",
         ScoreResult::Wrong => "result-card wrong",
     };
-    let (direction_text, direction_class) = get_direction_indicator(user_answer, correct);
+    let (direction_text, direction_class) = if result == ScoreResult::WrongSign {
+        ("Right magnitude, wrong sign", "sign")
+    } else {
+        get_direction_indicator(score.log_error)
+    };
     let label = result.label();
+    let correct = challenge.answer();
+    let (low, high) = user_answer.bounds();
+    // Exact answers need no explanation
+    let diagnosis = (score.result != ScoreResult::Exact)
+        .then(|| diagnose(&challenge, low.geometric_mean(high)))
+        .flatten();
+    let (low_error, high_error) = Estimate::new(correct)
+        .map_or((score.log_error, score.log_error), |correct| (log_error(low, correct), log_error(high, correct)));
+    let distance = score.log_error.abs();
+    let exponent_wrong = !score.components.exponent_correct();
+    let mantissa_wrong = score.components.mantissa_percent().abs() >= MANTISSA_TOLERANCE;
 
     view! {
         <div>
             <div class=result_class>
                 <div class="result-label">{label}</div>
+                <div class="result-points">
+                    {format!("+{} points ({} scoring)", score.points, score.rule.label())}
+                    " in " {format_seconds(seconds)}
+                </div>
                 <div class="result-details">
                     <div>
                         "You: "
-                        <span class="your-answer">{format_number(user_answer)}</span>
+                        <span class="your-answer">{format_answer(user_answer)}</span>
                         {(!direction_text.is_empty()).then(|| view! {
                             <span class=format!("direction {direction_class}")>{direction_text}</span>
                         })}
@@ -113,6 +353,19 @@ fn ResultCard(
                         <span class="correct-answer">{format_number(correct)}</span>
                     </div>
                 </div>
+                <div class="result-distance">
+                    {describe_distance(distance, low.is_negative() != (correct < 0.0))}
+                </div>
+                {diagnosis.map(|mistake| view! { <div class="result-diagnosis">{mistake.feedback()}</div> })}
+                <NumberLine low_error=low_error high_error=high_error estimate_error=score.log_error />
+                <div class="result-components">
+                    <div class:component-wrong=exponent_wrong>
+                        "Exponent: " {describe_exponent(score.components)}
+                    </div>
+                    <div class:component-wrong=mantissa_wrong>
+                        "Mantissa: " {describe_mantissa(score.components)}
+                    </div>
+                </div>
             </div>
             <button class="next-btn" on:click=move |_| on_next()>
                 {move || if current_index.get() + 1 >= PROBLEMS_PER_DAY { "See Results" } else { "Next Problem" }}
@@ -129,11 +382,18 @@ fn MagnitudeButtons(
 ) -> impl IntoView {
     let adjust = move |multiplier: f64| {
         set_answer_value.update(|v| {
-            let new_val = if *v < 1.0 { 1000.0 } else { *v * multiplier };
-            *v = new_val.clamp(0.0, 1e15);
+            let new_val = if v.abs() < 1.0 { 1000.0 } else { v.abs() * multiplier };
+            *v = v.signum() * new_val.clamp(0.0, 1e15);
         });
         let val = answer_value.get();
-        if val >= 1.0 {
+        if val.abs() >= 1.0 {
+            set_user_input.set(format_answer_display(val));
+        }
+    };
+    let flip_sign = move || {
+        set_answer_value.update(|v| *v = -*v);
+        let val = answer_value.get();
+        if val.abs() >= 1.0 {
             set_user_input.set(format_answer_display(val));
         }
     };
@@ -142,10 +402,10 @@ fn MagnitudeButtons(
         <div>
             {move || {
                 let val = answer_value.get();
-                let class_name = if val >= 1.0 { "answer-display has-value" } else { "answer-display" };
+                let class_name = if val.abs() >= 1.0 { "answer-display has-value" } else { "answer-display" };
                 view! {
                     <div class=class_name>
-                        {if val >= 1.0 {
+                        {if val.abs() >= 1.0 {
                             view! { <span class="answer-value">{format_answer_display(val)}</span> }.into_view()
                         } else {
                             view! { <span class="answer-placeholder">"Use buttons below"</span> }.into_view()
@@ -166,24 +426,41 @@ fn MagnitudeButtons(
                     <button class="mag-btn decrease" on:click=move |_| adjust(0.1)>"/10"</button>
                     <button class="mag-btn decrease" on:click=move |_| adjust(0.5)>"/2"</button>
                 </div>
+                <div class="magnitude-row">
+                    <button class="mag-btn sign" on:click=move |_| flip_sign()>"±"</button>
+                </div>
             </div>
         </div>
     }
 }
 
+/// The ± button in Exponent mode: a bare exponent's own sign is the
+/// exponent's, so a negative answer is written "-10^11".
+fn flip_exponent_sign(input: &str) -> String {
+    let input = input.trim();
+    match input.strip_prefix(['-', '−']) {
+        Some(rest) if rest.trim_start().starts_with("10^") => rest.trim_start().to_string(),
+        _ if input.starts_with("10^") => format!("-{input}"),
+        _ => format!("-10^{input}"),
+    }
+}
+
 #[component]
 fn TextInput(
     user_input: ReadSignal<String>,
     set_user_input: WriteSignal<String>,
     set_answer_value: WriteSignal<f64>,
+    parsed: Memo<Result<Answer, ParseError>>,
+    answer_mode: Signal<AnswerMode>,
     on_submit: Box<dyn Fn()>,
     has_answer: Box<dyn Fn() -> bool>,
 ) -> impl IntoView {
+    let exponent_only = move || answer_mode.get() == AnswerMode::Exponent;
     view! {
         <div class="text-input-wrapper">
             <input
                 type="text"
-                placeholder="e.g. 400B, 4e11"
+                placeholder=move || if exponent_only() { "e.g. 11, 11.6 or -10^11" } else { "e.g. 400B, 4e11" }
                 prop:value=move || user_input.get()
                 on:input=move |ev| {
                     set_user_input.set(event_target_value(&ev));
@@ -193,41 +470,265 @@ fn TextInput(
                     if ev.key() == "Enter" && has_answer() { on_submit(); }
                 }
             />
-            <div class="input-hint">"Formats: 400B, 400 billion, 4e11, 4x10^11"</div>
+            {move || exponent_only().then(|| view! {
+                <button
+                    class="mag-btn sign"
+                    aria-label="Flip the sign of the answer"
+                    on:click=move |_| set_user_input.update(|input| *input = flip_exponent_sign(input))
+                >"±"</button>
+            })}
+            {move || {
+                let input = user_input.get();
+                if input.trim().is_empty() {
+                    return None;
+                }
+                Some(match parsed.get() {
+                    Ok(answer) => view! {
+                        <div class="parse-preview" aria-live="polite">{format_preview(answer)}</div>
+                    }.into_view(),
+                    Err(err) => view! {
+                        <div class="parse-error" aria-live="polite">
+                            <pre class="parse-error-source">{input.clone()}"\n"{caret_line(&input, &err)}</pre>
+                            <div class="parse-error-message">{err.message()}</div>
+                        </div>
+                    }.into_view(),
+                })
+            }}
+            <div class="input-hint">
+                {move || if exponent_only() {
+                    "Power of ten only: 11 means 10^11, 11.6 is about 4 x 10^11; ± makes it -10^11"
+                } else {
+                    "Formats: 400B, 400 billion, 4e11, 4x10^11, four hundred billion"
+                }}
+            </div>
         </div>
     }
 }
 
 #[component]
-fn InputModeToggle(input_mode: ReadSignal<bool>, set_input_mode: WriteSignal<bool>) -> impl IntoView {
+fn InputModeToggle(input_mode: ReadSignal<InputMode>, set_input_mode: WriteSignal<InputMode>) -> impl IntoView {
+    let mode_button = move |mode: InputMode, label: &'static str| {
+        view! {
+            <button
+                class="mag-btn"
+                style:background=move || if input_mode.get() == mode { "var(--accent)" } else { "var(--bg-card)" }
+                style:color=move || if input_mode.get() == mode { "#fff" } else { "var(--text-primary)" }
+                on:click=move |_| set_input_mode.set(mode)
+            >{label}</button>
+        }
+    };
     view! {
         <div style="display: flex; justify-content: center; gap: 0.5rem; margin-bottom: 1rem;">
+            {mode_button(InputMode::Buttons, "Buttons")}
+            {mode_button(InputMode::Text, "Type")}
+            {mode_button(InputMode::Exponent, "Exponent")}
+            {mode_button(InputMode::Interval, "Interval")}
+        </div>
+    }
+}
+
+#[component]
+fn NotationSettings(
+    suffix_style: ReadSignal<SuffixStyle>,
+    set_suffix_style: WriteSignal<SuffixStyle>,
+    decimal: ReadSignal<DecimalSeparator>,
+    set_decimal: WriteSignal<DecimalSeparator>,
+) -> impl IntoView {
+    let style_button = move |style: SuffixStyle, label: &'static str| {
+        view! {
             <button
-                class="mag-btn"
-                style:background=move || if input_mode.get() { "var(--accent)" } else { "var(--bg-card)" }
-                style:color=move || if input_mode.get() { "#fff" } else { "var(--text-primary)" }
-                on:click=move |_| set_input_mode.set(true)
-            >"Buttons"</button>
+                class="style-btn"
+                class:active=move || suffix_style.get() == style
+                on:click=move |_| set_suffix_style.set(style)
+            >{label}</button>
+        }
+    };
+    let decimal_button = move |separator: DecimalSeparator, label: &'static str| {
+        view! {
             <button
-                class="mag-btn"
-                style:background=move || if input_mode.get() { "var(--bg-card)" } else { "var(--accent)" }
-                style:color=move || if input_mode.get() { "var(--text-primary)" } else { "#fff" }
-                on:click=move |_| set_input_mode.set(false)
-            >"Type"</button>
+                class="style-btn"
+                class:active=move || decimal.get() == separator
+                on:click=move |_| set_decimal.set(separator)
+            >{label}</button>
+        }
+    };
+    view! {
+        <div class="notation-setting">
+            <span>"\"5m\" means"</span>
+            {style_button(SuffixStyle::Finance, "5 million")}
+            {style_button(SuffixStyle::Physics, "0.005 (SI)")}
+        </div>
+        <div class="notation-setting">
+            <span>"Decimals"</span>
+            {decimal_button(DecimalSeparator::Point, "4.5")}
+            {decimal_button(DecimalSeparator::Comma, "4,5")}
         </div>
     }
 }
 
-fn count_results(results: &[(ScoreResult, f64, f64)]) -> (usize, usize, usize) {
-    let exact = results.iter().filter(|(r, _, _)| matches!(r, ScoreResult::Exact | ScoreResult::Close)).count();
-    let partial = results.iter().filter(|(r, _, _)| matches!(r, ScoreResult::Partial)).count();
-    let wrong = results.iter().filter(|(r, _, _)| matches!(r, ScoreResult::Wrong)).count();
-    (exact, partial, wrong)
+#[component]
+fn ScoringSettings(scoring_rule: ReadSignal<RuleKind>, set_scoring_rule: WriteSignal<RuleKind>) -> impl IntoView {
+    view! {
+        <div class="notation-setting">
+            <span>"Scoring"</span>
+            {RuleKind::ALL.map(|kind| view! {
+                <button
+                    class="style-btn"
+                    class:active=move || scoring_rule.get() == kind
+                    on:click=move |_| set_scoring_rule.set(kind)
+                >{kind.label()}</button>
+            }).collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn SignedSetting(signed: ReadSignal<bool>, set_signed: WriteSignal<bool>) -> impl IntoView {
+    view! {
+        <div class="notation-setting">
+            <span>"Negative numbers"</span>
+            {[(false, "Off"), (true, "On")].map(|(value, label)| view! {
+                <button
+                    class="style-btn"
+                    class:active=move || signed.get() == value
+                    on:click=move |_| set_signed.set(value)
+                >{label}</button>
+            }).collect_view()}
+        </div>
+    }
+}
+
+/// e.g. "You underestimate divisions by 0.27 OOM on average (12 answers)".
+fn bias_summary(operation: Operation, bias: f64, count: usize) -> String {
+    let problems = match operation {
+        Operation::Multiply => "multiplications",
+        Operation::Divide => "divisions",
+    };
+    let answers = if count == 1 { "1 answer".to_string() } else { format!("{count} answers") };
+    if bias.abs() < 0.05 {
+        format!("No clear bias on {problems} ({answers})")
+    } else {
+        let direction = if bias > 0.0 { "overestimate" } else { "underestimate" };
+        format!("You {direction} {problems} by {:.2} OOM on average ({answers})", bias.abs())
+    }
+}
+
+/// Bias per operation over every recorded day and today, clamped to ±1 OOM
+/// for the meter.
+#[component]
+fn BiasMeter(answers: Memo<Vec<ProblemRecord>>) -> impl IntoView {
+    let row = move |operation: Operation| {
+        answers.with(|answers| bias_by_operation(answers, operation)).map(|(bias, count)| {
+            let position = 50.0 + bias.clamp(-1.0, 1.0) * 50.0;
+            view! {
+                <div class="bias-row">
+                    <div class="bias-text">{bias_summary(operation, bias, count)}</div>
+                    <div class="bias-track">
+                        <div class="bias-center"></div>
+                        <div class="bias-marker" style:left=format!("{position}%")></div>
+                    </div>
+                    <div class="bias-scale"><span>"Too low"</span><span>"Too high"</span></div>
+                </div>
+            }
+        })
+    };
+    view! {
+        <div class="bias-meter">
+            {move || row(Operation::Multiply)}
+            {move || row(Operation::Divide)}
+        </div>
+    }
+}
+
+#[component]
+fn IntervalWidth(interval_width: ReadSignal<f64>, set_interval_width: WriteSignal<f64>) -> impl IntoView {
+    view! {
+        <div class="input-hint">
+            {format!(
+                "Give a range you are {:.0}% sure of, e.g. 300B-600B, or a point and a width",
+                INTERVAL_COVERAGE * 100.0
+            )}
+        </div>
+        <div class="notation-setting">
+            <span>"Point ±"</span>
+            {INTERVAL_WIDTHS.map(|width| view! {
+                <button
+                    class="style-btn"
+                    class:active=move || interval_width.get() == width
+                    on:click=move |_| set_interval_width.set(width)
+                >{format!("{width} OOM")}</button>
+            }).collect_view()}
+        </div>
+    }
+}
+
+/// e.g. "Your 80% intervals contained the truth 54% of the time (7 of 13)",
+/// over every interval answer on record.
+#[component]
+fn CalibrationReport(answers: Memo<Vec<ProblemRecord>>) -> impl IntoView {
+    move || {
+        answers.with(|answers| {
+            let attempts: Vec<Attempt> = answers
+                .iter()
+                .filter(|r| r.rule == RuleKind::IntervalScore)
+                .filter_map(ProblemRecord::attempt)
+                .collect();
+            hit_rate(&attempts).map(|rate| {
+                let hits = attempts.iter().filter(|a| a.is_interval() && a.contains()).count();
+                let total = attempts.iter().filter(|a| a.is_interval()).count();
+                view! {
+                    <div class="calibration-report">
+                        {format!(
+                            "Your {:.0}% intervals contained the truth {:.0}% of the time ({hits} of {total})",
+                            INTERVAL_COVERAGE * 100.0,
+                            rate * 100.0
+                        )}
+                    </div>
+                }
+            })
+        })
+    }
+}
+
+fn count_results(results: &[ProblemResult]) -> (usize, usize, usize, usize) {
+    let count = |f: fn(ScoreResult) -> bool| results.iter().filter(|r| f(r.score.result)).count();
+    let exact = count(|r| matches!(r, ScoreResult::Exact | ScoreResult::Close));
+    let partial = count(|r| r == ScoreResult::Partial);
+    let wrong_sign = count(|r| r == ScoreResult::WrongSign);
+    let wrong = count(|r| r == ScoreResult::Wrong);
+    (exact, partial, wrong_sign, wrong)
+}
+
+fn format_rating(rating: Rating) -> String {
+    format!("{:.0} ± {:.0}", rating.value, 2.0 * rating.deviation)
+}
+
+#[component]
+fn RatingSummary(rating: ReadSignal<RatingState>) -> impl IntoView {
+    move || {
+        let state = rating.get();
+        let change = state.change_today();
+        let change_class = if change >= 0.0 { "rating-change up" } else { "rating-change down" };
+        view! {
+            <div class="rating-summary">
+                <div class="rating-label">"Rating"</div>
+                <div class="rating-value">
+                    {format_rating(state.overall)}
+                    <span class=change_class>{format!("{change:+.0} since last session")}</span>
+                </div>
+                <div class="rating-skills">
+                    {format!("x {} · / {}", format_rating(state.multiply), format_rating(state.divide))}
+                </div>
+            </div>
+        }
+    }
 }
 
 #[component]
 fn CompleteScreen(
-    results: ReadSignal<Vec<(ScoreResult, f64, f64)>>,
+    results: ReadSignal<Vec<ProblemResult>>,
+    answers: Memo<Vec<ProblemRecord>>,
+    rating: ReadSignal<RatingState>,
     total_score: Box<dyn Fn() -> u32>,
 ) -> impl IntoView {
     view! {
@@ -237,7 +738,11 @@ fn CompleteScreen(
             <div class="complete-subtitle">{format!("out of {} points", PROBLEMS_PER_DAY * 100)}</div>
             <div class="score-breakdown">
                 {move || {
-                    let (exact, partial, wrong) = count_results(&results.get());
+                    let (exact, partial, wrong_sign, wrong) = count_results(&results.get());
+                    let total_seconds = results.with(|r| r.iter().map(|r| r.seconds).sum::<f64>());
+                    let times = results.with(|r| {
+                        r.iter().map(|r| format_seconds(r.seconds)).collect::<Vec<_>>().join(" · ")
+                    });
                     view! {
                         <>
                             <div class="breakdown-row">
@@ -248,43 +753,279 @@ fn CompleteScreen(
                                 <span class="breakdown-label">"Close (within 1 OOM)"</span>
                                 <span class="breakdown-value close">{partial}</span>
                             </div>
+                            {(wrong_sign > 0).then(|| view! {
+                                <div class="breakdown-row">
+                                    <span class="breakdown-label">"Right magnitude, wrong sign"</span>
+                                    <span class="breakdown-value close">{wrong_sign}</span>
+                                </div>
+                            })}
                             <div class="breakdown-row">
                                 <span class="breakdown-label">"Off (1+ OOM)"</span>
                                 <span class="breakdown-value wrong">{wrong}</span>
                             </div>
+                            <div class="breakdown-row">
+                                <span class="breakdown-label">"Total time"</span>
+                                <span class="breakdown-value">{format_seconds(total_seconds)}</span>
+                            </div>
+                            <div class="breakdown-row">
+                                <span class="breakdown-label">"Times"</span>
+                                <span class="breakdown-value">{times}</span>
+                            </div>
                         </>
                     }
                 }}
             </div>
+            <RatingSummary rating=rating />
+            <BiasMeter answers=answers />
+            <CalibrationReport answers=answers />
             <div class="come-back">"New problems tomorrow!"</div>
         </div>
     }
 }
 
+/// One point per day on a plain SVG line, labelled with its extremes and the
+/// first and last dates. `baseline` draws a reference line, such as zero bias.
+#[component]
+fn LineChart(
+    title: String,
+    values: Vec<f64>,
+    dates: (String, String),
+    format: fn(f64) -> String,
+    #[prop(optional)] baseline: Option<f64>,
+) -> impl IntoView {
+    const WIDTH: f64 = 300.0;
+    const LEFT: f64 = 48.0;
+    const RIGHT: f64 = 292.0;
+    const TOP: f64 = 8.0;
+    const BOTTOM: f64 = 96.0;
+    let (low, high) = value_range(&values, baseline);
+    #[allow(clippy::cast_precision_loss)]
+    let x = |i: usize| match values.len() {
+        0 | 1 => (LEFT + RIGHT) / 2.0,
+        n => LEFT + i as f64 / (n - 1) as f64 * (RIGHT - LEFT),
+    };
+    let y = move |value: f64| BOTTOM - (value - low) / (high - low) * (BOTTOM - TOP);
+    let points = values.iter().enumerate().map(|(i, &v)| format!("{:.1},{:.1}", x(i), y(v))).collect::<Vec<_>>();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    view! {
+        <div class="chart">
+            <div class="chart-title">{title.clone()}</div>
+            <svg class="line-chart" viewBox=format!("0 0 {WIDTH} 116") role="img" aria-label=title>
+                <line class="axis" x1=LEFT y1=BOTTOM x2=RIGHT y2=BOTTOM />
+                {baseline.map(|b| view! { <line class="baseline" x1=LEFT y1=y(b) x2=RIGHT y2=y(b) /> })}
+                <text class="tick-label" x=LEFT - 4.0 y=y(max) + 3.0 text-anchor="end">{format(max)}</text>
+                {(max > min).then(|| view! {
+                    <text class="tick-label" x=LEFT - 4.0 y=y(min) + 3.0 text-anchor="end">{format(min)}</text>
+                })}
+                <polyline class="series" points=points.join(" ") />
+                {values.iter().enumerate().map(|(i, &v)| view! {
+                    <circle class="series-point" cx=x(i) cy=y(v) r="2.5" />
+                }).collect_view()}
+                <text class="tick-label" x=LEFT y="112" text-anchor="start">{dates.0}</text>
+                <text class="tick-label" x=RIGHT y="112" text-anchor="end">{dates.1}</text>
+            </svg>
+        </div>
+    }
+}
+
+/// Horizontal SVG bars: the share of answers within 0.5 OOM, per category.
+#[component]
+fn AccuracyChart(rows: Vec<(&'static str, Accuracy)>) -> impl IntoView {
+    const ROW: f64 = 22.0;
+    const LEFT: f64 = 70.0;
+    const RIGHT: f64 = 230.0;
+    #[allow(clippy::cast_precision_loss)]
+    let height = rows.len() as f64 * ROW;
+    view! {
+        <div class="chart">
+            <div class="chart-title">"Accuracy (within 0.5 OOM)"</div>
+            <svg
+                class="bar-chart"
+                viewBox=format!("0 0 300 {height}")
+                role="img"
+                aria-label="Accuracy by kind of problem"
+            >
+                {rows.into_iter().enumerate().map(|(i, (label, accuracy))| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let top = i as f64 * ROW;
+                    let fraction = accuracy.fraction();
+                    view! {
+                        <text class="tick-label" x=LEFT - 6.0 y=top + 14.0 text-anchor="end">{label}</text>
+                        <rect class="bar-track" x=LEFT y=top + 4.0 width=RIGHT - LEFT height="12" />
+                        <rect
+                            class="bar"
+                            x=LEFT y=top + 4.0 width=fraction.unwrap_or(0.0) * (RIGHT - LEFT) height="12"
+                        />
+                        <text class="tick-label" x=RIGHT + 6.0 y=top + 14.0 text-anchor="start">
+                            {fraction.map_or_else(
+                                || "—".to_string(),
+                                |f| format!("{:.0}% of {}", f * 100.0, accuracy.total),
+                            )}
+                        </text>
+                    }
+                }).collect_view()}
+            </svg>
+        </div>
+    }
+}
+
+/// Trends across every recorded day, computed from the stored history.
+#[component]
+fn StatsScreen(history: History) -> impl IntoView {
+    let days = daily(&history);
+    let (Some(first), Some(last)) = (days.first(), days.last()) else {
+        return view! { <div class="stats-empty">"Finish a day to see your stats"</div> }.into_view();
+    };
+    let dates = (first.date.clone(), last.date.clone());
+    let series = |value: fn(&DayStats) -> f64| days.iter().map(value).collect::<Vec<_>>();
+    let mut rows = vec![
+        ("Multiply", accuracy_by_operation(&history, Operation::Multiply)),
+        ("Divide", accuracy_by_operation(&history, Operation::Divide)),
+    ];
+    rows.extend(Skill::ALL.map(|skill| (skill.label(), accuracy_by_skill(&history, skill))));
+    view! {
+        <div class="stats-screen">
+            <div class="complete-title">"Stats"</div>
+            <LineChart
+                title="Daily score".into()
+                values=series(|d| f64::from(d.score))
+                dates=dates.clone()
+                format=|v| format!("{v:.0}")
+            />
+            <LineChart
+                title=format!("Average error, rolling {ROLLING_WINDOW} days")
+                values=rolling_mean(&series(|d| d.mean_distance), ROLLING_WINDOW)
+                dates=dates.clone()
+                format=|v| format!("{v:.2}")
+            />
+            <AccuracyChart rows=rows />
+            <LineChart
+                title="Bias (above zero: too high)".into()
+                values=series(|d| d.bias)
+                dates=dates.clone()
+                format=|v| format!("{v:+.2}")
+                baseline=0.0
+            />
+            <LineChart
+                title="Average answer time".into()
+                values=series(|d| d.mean_seconds)
+                dates=dates
+                format=format_seconds
+            />
+        </div>
+    }
+    .into_view()
+}
 
 #[component]
 pub fn App() -> impl IntoView {
-    let seed = get_daily_seed();
-    let challenges = store_value(generate_challenges(seed, PROBLEMS_PER_DAY));
-    let (current_index, set_current_index) = create_signal(0usize);
+    let date = today();
+    let seed = get_daily_seed(&date);
+    let storage = store_value(browser_storage());
+    // Why progress isn't being kept, if it isn't; the session carries on regardless
+    let initial_problem = storage.with_value(|s| (!s.is_persistent()).then_some(StorageError::Unavailable));
+    let (storage_problem, set_storage_problem) = create_signal(initial_problem);
+    let persist = move |result: Result<(), StorageError>| {
+        if let Err(err) = result {
+            set_storage_problem.set(Some(err));
+        }
+    };
+    let stored_history = storage.with_value(|s| history::stored(s.as_ref()));
+    let saved = storage.with_value(|s| load(s.as_ref(), SESSION_KEY));
+    let session = Session::resume(saved, stored_history.session(&date, seed), seed, PROBLEMS_PER_DAY);
+    let (current_index, set_current_index) = create_signal(session.index);
     let (user_input, set_user_input) = create_signal(String::new());
     let (answer_value, set_answer_value) = create_signal(0.0f64);
-    let (submitted, set_submitted) = create_signal(false);
-    let (score_results, set_score_results) = create_signal(Vec::<(ScoreResult, f64, f64)>::new());
-    let (input_mode, set_input_mode) = create_signal(true);
+    let (submitted, set_submitted) = create_signal(session.is_submitted());
+    let (score_results, set_score_results) = create_signal(session.results);
+    let (signed, set_signed) = create_signal(session.signed);
+    let challenges = create_memo(move |_| generate_challenges(seed, PROBLEMS_PER_DAY, signed.get()));
+    // Every answer so far: earlier days from the history, then today's
+    let past = store_value(past_problems(&stored_history, &date, seed));
+    let answers = create_memo(move |_| {
+        let today = score_results.with(|r| r.iter().map(ProblemRecord::from).collect::<Vec<_>>());
+        past.with_value(|past| past.iter().copied().chain(today).collect())
+    });
+    let (scoring_rule, set_scoring_rule) = create_signal(session.rule);
+    let (interval_width, set_interval_width) = create_signal(0.5f64);
+    let (problem_started, set_problem_started) = create_signal(session.started);
+    let saved_rating: RatingState = storage.with_value(|s| load(s.as_ref(), RATING_KEY)).unwrap_or_default();
+    let (rating, set_rating) = create_signal(saved_rating.begin_day(local_day()));
+    // Effects run after rendering, so the clock starts once the problem is on
+    // screen. A resumed problem keeps its saved start, so reloading doesn't
+    // reset the clock.
+    create_effect(move |previous: Option<usize>| {
+        let index = current_index.get();
+        if previous.is_some() || problem_started.get_untracked().is_none() {
+            set_problem_started.set(Some(js_sys::Date::now()));
+        }
+        index
+    });
+    // Saved on every change, so a reload resumes here and a finished day stays finished
+    create_effect(move |_| {
+        let session = Session {
+            seed,
+            index: current_index.get(),
+            results: score_results.get(),
+            started: problem_started.get(),
+            rule: scoring_rule.get(),
+            signed: signed.get(),
+        };
+        persist(storage.with_value(|s| save(s.as_ref(), SESSION_KEY, &session)));
+    });
+    let (show_stats, set_show_stats) = create_signal(false);
+    let (input_mode, set_input_mode) = create_signal(InputMode::Buttons);
+    let (suffix_style, set_suffix_style) = create_signal(SuffixStyle::Finance);
+    let (decimal, set_decimal) = create_signal(browser_decimal_separator());
 
-    let current_challenge = move || challenges.with_value(|c| c.get(current_index.get()).copied());
-    let total_score = move || score_results.get().iter().map(|(r, _, _)| r.points()).sum::<u32>();
+    let current_challenge = move || challenges.with(|c| c.get(current_index.get()).copied());
+    let total_score = move || score_results.get().iter().map(|r| r.score.points).sum::<u32>();
     let is_complete = move || current_index.get() >= PROBLEMS_PER_DAY;
-    let has_answer = move || answer_value.get() >= 1.0 || !user_input.get().is_empty();
+    // Also runs when a finished day is reloaded; the history keeps the first record
+    create_effect(move |_| {
+        if is_complete() {
+            let problems = score_results.with(|r| r.iter().map(ProblemRecord::from).collect());
+            let record = SessionRecord { date: date.clone(), seed, problems };
+            persist(storage.with_value(|s| history::append(s.as_ref(), record)).map(|_| ()));
+        }
+    });
+    let parsed = create_memo(move |_| {
+        let options = ParseOptions {
+            mode: input_mode.get().answer_mode(),
+            suffixes: suffix_style.get(),
+            decimal: decimal.get(),
+        };
+        parse_answer(&user_input.get(), options)
+    });
+    // The buttons' value only counts while they're showing; in the typed modes
+    // Submit needs the text to parse
+    let button_answer = move || {
+        let value = answer_value.get();
+        (input_mode.get() == InputMode::Buttons && value.abs() >= 1.0).then_some(value)
+    };
+    let has_answer = move || button_answer().is_some() || parsed.with(Result::is_ok);
 
     let do_submit = move || {
         if submitted.get() { return; }
-        let user_answer = if answer_value.get() >= 1.0 { Some(answer_value.get()) } else { parse_answer(&user_input.get()) };
-        if let Some(challenge) = current_challenge() {
-            if let Some(answer) = user_answer {
-                let correct = challenge.answer();
-                set_score_results.update(|r| r.push((evaluate(answer, correct), answer, correct)));
+        let user_answer = match button_answer() {
+            Some(value) => Estimate::new(value).ok().map(Answer::Point),
+            None => parsed.get().ok(),
+        };
+        // Interval answers always use the interval rule
+        let (user_answer, rule) = if input_mode.get() == InputMode::Interval {
+            (user_answer.map(|answer| widen(answer, interval_width.get())), RuleKind::IntervalScore)
+        } else {
+            (user_answer, scoring_rule.get())
+        };
+        let now = js_sys::Date::now();
+        let seconds = (now - problem_started.get_untracked().unwrap_or(now)).max(0.0) / 1000.0;
+        if let (Some(challenge), Some(answer)) = (current_challenge(), user_answer) {
+            if let Some(attempt) = attempt(challenge, answer, seconds) {
+                let score = rule.rule().score(&attempt);
+                set_score_results.update(|r| r.push(ProblemResult { challenge, answer, score, seconds }));
+                set_rating.update(|state| *state = state.record(&challenge, &attempt));
+                persist(storage.with_value(|s| save(s.as_ref(), RATING_KEY, &rating.get_untracked())));
                 set_submitted.set(true);
             }
         }
@@ -302,59 +1043,198 @@ pub fn App() -> impl IntoView {
             <header class="header">
                 <h1>"OOM Trainer"</h1>
                 <div class="subtitle">"Order of Magnitude Estimation"</div>
+                <button class="stats-toggle" on:click=move |_| set_show_stats.update(|shown| *shown = !*shown)>
+                    {move || if show_stats.get() { "Back" } else { "Stats" }}
+                </button>
             </header>
-            <Show when=is_complete fallback=move || view! {
-                <ProgressBar current=current_index total_score=Box::new(total_score) />
-                <Show when=move || current_challenge().is_some() fallback=|| view! { <div>"Loading..."</div> }>
-                    {move || {
-                        let challenge = current_challenge().unwrap();
-                        view! {
-                            <div>
-                                <ProblemCard challenge=challenge />
-                                <Show
-                                    when=move || !submitted.get()
-                                    fallback=move || {
-                                        score_results.get().last().copied().map_or_else(
-                                            || view! { <div></div> }.into_view(),
-                                            |(result, user_answer, correct)| view! {
-                                                <ResultCard result=result user_answer=user_answer correct=correct
-                                                    current_index=current_index on_next=Box::new(do_next) />
-                                            }.into_view()
-                                        )
-                                    }
-                                >
-                                    <div class="input-section">
-                                        <InputModeToggle input_mode=input_mode set_input_mode=set_input_mode />
-                                        <Show
-                                            when=move || input_mode.get()
-                                            fallback=move || view! {
-                                                <TextInput
-                                                    user_input=user_input
-                                                    set_user_input=set_user_input
+            {move || storage_problem.get().map(|err| view! {
+                <div class="storage-notice">{format!("Progress isn't being saved: {err}")}</div>
+            })}
+            <Show when=move || !show_stats.get() fallback=move || view! {
+                <StatsScreen history=storage.with_value(|s| history::stored(s.as_ref())) />
+            }>
+                <Show when=is_complete fallback=move || view! {
+                    <ProgressBar current=current_index total_score=Box::new(total_score) />
+                    <Show when=move || current_challenge().is_some() fallback=|| view! { <div>"Loading..."</div> }>
+                        {move || {
+                            let challenge = current_challenge().unwrap();
+                            view! {
+                                <div>
+                                    <ProblemCard challenge=challenge />
+                                    <Show
+                                        when=move || !submitted.get()
+                                        fallback=move || {
+                                            score_results.get().last().copied().map_or_else(
+                                                || view! { <div></div> }.into_view(),
+                                                |result| view! {
+                                                    <ResultCard score=result.score user_answer=result.answer
+                                                        challenge=result.challenge seconds=result.seconds
+                                                        current_index=current_index on_next=Box::new(do_next) />
+                                                }.into_view()
+                                            )
+                                        }
+                                    >
+                                        <div class="input-section">
+                                            <InputModeToggle input_mode=input_mode set_input_mode=set_input_mode />
+                                            // Settings are fixed once the day's first answer is in
+                                            {move || score_results.with(Vec::is_empty).then(|| view! {
+                                                <ScoringSettings scoring_rule=scoring_rule set_scoring_rule=set_scoring_rule />
+                                                <SignedSetting signed=signed set_signed=set_signed />
+                                            })}
+                                            <Show
+                                                when=move || input_mode.get() == InputMode::Buttons
+                                                fallback=move || view! {
+                                                    <>
+                                                    <TextInput
+                                                        user_input=user_input
+                                                        set_user_input=set_user_input
+                                                        set_answer_value=set_answer_value
+                                                        parsed=parsed
+                                                        answer_mode=Signal::derive(move || input_mode.get().answer_mode())
+                                                        on_submit=Box::new(do_submit)
+                                                        has_answer=Box::new(has_answer)
+                                                    />
+                                                    {move || (input_mode.get() == InputMode::Interval).then(|| view! {
+                                                        <IntervalWidth
+                                                            interval_width=interval_width
+                                                            set_interval_width=set_interval_width
+                                                        />
+                                                    })}
+                                                    {move || matches!(input_mode.get(), InputMode::Text | InputMode::Interval).then(|| view! {
+                                                        <NotationSettings
+                                                            suffix_style=suffix_style
+                                                            set_suffix_style=set_suffix_style
+                                                            decimal=decimal
+                                                            set_decimal=set_decimal
+                                                        />
+                                                    })}
+                                                    </>
+                                                }
+                                            >
+                                                <MagnitudeButtons
+                                                    answer_value=answer_value
                                                     set_answer_value=set_answer_value
-                                                    on_submit=Box::new(do_submit)
-                                                    has_answer=Box::new(has_answer)
+                                                    set_user_input=set_user_input
                                                 />
-                                            }
-                                        >
-                                            <MagnitudeButtons
-                                                answer_value=answer_value
-                                                set_answer_value=set_answer_value
-                                                set_user_input=set_user_input
-                                            />
-                                        </Show>
-                                    </div>
-                                    <button class="submit-btn" on:click=move |_| do_submit() prop:disabled=move || !has_answer()>
-                                        "Submit"
-                                    </button>
-                                </Show>
-                            </div>
-                        }
-                    }}
+                                            </Show>
+                                        </div>
+                                        <button class="submit-btn" on:click=move |_| do_submit() prop:disabled=move || !has_answer()>
+                                            "Submit"
+                                        </button>
+                                    </Show>
+                                </div>
+                            }
+                        }}
+                    </Show>
+                }>
+                    <CompleteScreen
+                        results=score_results
+                        answers=answers
+                        rating=rating
+                        total_score=Box::new(total_score)
+                    />
                 </Show>
-            }>
-                <CompleteScreen results=score_results total_score=Box::new(total_score) />
             </Show>
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn parse(input: &str) -> Result<Answer, TestCaseError> {
+        parse_answer(input, ParseOptions::default())
+            .map_err(|err| TestCaseError::fail(format!("{input:?} did not parse: {err}")))
+    }
+
+    /// `format_answer_display` keeps three significant figures, or whole units below 1000.
+    fn display_tolerance(x: f64) -> f64 {
+        (x * 0.005).max(0.5) + x * 1e-12
+    }
+
+    proptest! {
+        #[test]
+        fn format_answer_display_round_trips(exponent in 0.0..15.0f64) {
+            let x = 10_f64.powf(exponent);
+            let text = format_answer_display(x);
+            let parsed = parse(&text)?.bounds().0.get();
+            prop_assert!((parsed - x).abs() <= display_tolerance(x), "{x} -> {text:?} -> {parsed}");
+        }
+
+        #[test]
+        fn format_answer_round_trips_ranges(low_exp in 3.0..14.0f64, width in 0.1..3.0f64) {
+            let (low, high) = (10_f64.powf(low_exp), 10_f64.powf(low_exp + width));
+            let range = Answer::Range { low: Estimate::new(low).unwrap(), high: Estimate::new(high).unwrap() };
+            let text = format_answer(range);
+            let Answer::Range { low: parsed_low, high: parsed_high } = parse(&text)? else {
+                return Err(TestCaseError::fail(format!("{text:?} is not a range")));
+            };
+            prop_assert!((parsed_low.get() / low - 1.0).abs() <= 0.05 + 1e-9, "{text:?}");
+            prop_assert!((parsed_high.get() / high - 1.0).abs() <= 0.05 + 1e-9, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_widen() {
+        let point = |x| Answer::Point(Estimate::new(x).unwrap());
+        let (low, high) = widen(point(1e6), 1.0).bounds();
+        assert!((low.get() - 1e5).abs() < 1e-6 && (high.get() - 1e7).abs() < 1e-3);
+        let (low, high) = widen(point(-1e6), 1.0).bounds();
+        assert!(low.get() < high.get() && high.get() < 0.0);
+        let range = Answer::Range { low: Estimate::new(3e5).unwrap(), high: Estimate::new(6e5).unwrap() };
+        assert_eq!(widen(range, 1.0), range);
+    }
+
+    #[test]
+    fn test_flip_exponent_sign() {
+        assert_eq!(flip_exponent_sign("11"), "-10^11");
+        assert_eq!(flip_exponent_sign("-3"), "-10^-3");
+        assert_eq!(flip_exponent_sign("-10^11"), "10^11");
+        assert_eq!(flip_exponent_sign(" 10^11"), "-10^11");
+        let exponent = ParseOptions { mode: AnswerMode::Exponent, ..ParseOptions::default() };
+        for input in ["11", "-3", "11.6"] {
+            let flipped = parse_answer(&flip_exponent_sign(input), exponent).unwrap().bounds().0.get();
+            let original = parse_answer(input, exponent).unwrap().bounds().0.get();
+            assert_eq!(flipped, -original);
+            assert_eq!(flip_exponent_sign(&flip_exponent_sign(input)), format!("10^{input}"));
+        }
+    }
+
+    #[test]
+    fn test_format_seconds() {
+        assert_eq!(format_seconds(8.04), "8.0s");
+        assert_eq!(format_seconds(75.0), "1m 15s");
+        assert_eq!(format_seconds(119.6), "2m 00s");
+    }
+
+    #[test]
+    fn test_number_line_span() {
+        assert!((number_line_span(0.1, 0.1) - 1.5).abs() < 1e-9);
+        assert!((number_line_span(-2.0, 0.5) - 2.25).abs() < 1e-9);
+        // "1e300" against a 1e10 truth stays a few ticks wide
+        let span = number_line_span(290.0, 290.0);
+        assert!((span - NUMBER_LINE_MAX_SPAN).abs() < 1e-9);
+        assert_eq!(number_line_label(290.0, span), "you +290 →");
+        assert_eq!(number_line_label(-4.4, span), "you -4 ←");
+        assert_eq!(number_line_label(1.2, span), "you");
+    }
+
+    #[test]
+    fn test_describe_band() {
+        assert_eq!(describe_band(0.05), "Exact band (0–0.1 OOM)");
+        assert_eq!(describe_band(0.34), "Close band (0.1–0.5 OOM), 0.24 OOM short of Exact");
+        assert_eq!(describe_band(0.7), "Partial band (0.5–1 OOM), 0.20 OOM short of Close");
+        assert_eq!(describe_band(1.5), "outside every band, 0.50 OOM short of Partial");
+    }
+
+    #[test]
+    fn test_describe_distance() {
+        assert_eq!(describe_distance(0.05, false), "Off by 0.05 orders of magnitude: Exact band (0–0.1 OOM)");
+        assert_eq!(
+            describe_distance(0.0, true),
+            "Magnitude off by 0.00 orders of magnitude, but the sign is wrong, so no band applies"
+        );
+    }
+}
@@ -1,16 +1,27 @@
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
 use std::collections::hash_map::DefaultHasher;
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Challenge {
     pub num1: f64,
     pub num2: f64,
     pub is_division: bool,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Multiply,
+    Divide,
+}
+
 impl Challenge {
+    pub fn operation(&self) -> Operation {
+        if self.is_division { Operation::Divide } else { Operation::Multiply }
+    }
+
     pub fn answer(&self) -> f64 {
         if self.is_division {
             self.num1 / self.num2
@@ -18,23 +29,60 @@ impl Challenge {
             self.num1 * self.num2
         }
     }
+
+    /// Whether the mantissa step crosses a power of ten, so the exponent
+    /// needs a carry (or a borrow, for division).
+    pub fn carries(&self) -> bool {
+        let mantissa = |n: f64| n.abs() / 10_f64.powf(n.abs().log10().floor());
+        let (m1, m2) = (mantissa(self.num1), mantissa(self.num2));
+        match self.operation() {
+            Operation::Multiply => m1 * m2 >= 10.0,
+            Operation::Divide => m1 < m2,
+        }
+    }
 }
 
-pub fn get_daily_seed() -> u64 {
+/// Today's local date, "YYYY-MM-DD"
+pub fn today() -> String {
     let date = js_sys::Date::new_0();
     let year = date.get_full_year();
     let month = date.get_month() + 1;
     let day = date.get_date();
-    let date_str = format!("{year}-{month:02}-{day:02}");
+    format!("{year}-{month:02}-{day:02}")
+}
 
+pub fn get_daily_seed(date: &str) -> u64 {
     let mut hasher = DefaultHasher::new();
-    date_str.hash(&mut hasher);
+    date.hash(&mut hasher);
     hasher.finish()
 }
 
-pub fn generate_challenges(seed: u64, count: usize) -> Vec<Challenge> {
+/// Days since 1970-01-01 in the player's local time zone
+pub fn local_day() -> i64 {
+    let date = js_sys::Date::new_0();
+    let local_ms = date.get_time() - date.get_timezone_offset() * 60_000.0;
+    #[allow(clippy::cast_possible_truncation)]
+    let day = (local_ms / 86_400_000.0).floor() as i64;
+    day
+}
+
+/// Share of operands made negative when signed problems are on, e.g. a net
+/// outflow or a drop in temperature
+const NEGATIVE_SHARE: f64 = 0.15;
+
+pub fn generate_challenges(seed: u64, count: usize, signed: bool) -> Vec<Challenge> {
     let mut rng = ChaCha8Rng::seed_from_u64(seed);
-    (0..count).map(|_| generate_single(&mut rng)).collect()
+    // Signs come from a stream of their own, so each date's magnitudes are
+    // the same with or without them
+    let mut signs = ChaCha8Rng::seed_from_u64(seed);
+    signs.set_stream(1);
+    let mut sign = move || if signed && signs.gen_bool(NEGATIVE_SHARE) { -1.0 } else { 1.0 };
+    (0..count)
+        .map(|_| {
+            let challenge = generate_single(&mut rng);
+            Challenge { num1: sign() * challenge.num1, num2: sign() * challenge.num2, ..challenge }
+        })
+        .collect()
 }
 
 fn generate_single(rng: &mut ChaCha8Rng) -> Challenge {
@@ -73,3 +121,92 @@ pub fn format_number(n: f64) -> String {
         format!("{n:.1}")
     }
 }
+
+/// Scientific notation with a superscript exponent, e.g. "4.0 × 10¹¹".
+pub fn format_scientific(n: f64) -> String {
+    if n == 0.0 || !n.is_finite() {
+        return format!("{n}");
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let mut exponent = n.abs().log10().floor() as i32;
+    let mut mantissa = n / 10_f64.powi(exponent);
+    // 9.96 rounds up to "10.0", so carry into the exponent
+    if (mantissa.abs() * 10.0).round() >= 100.0 {
+        exponent += 1;
+        mantissa /= 10.0;
+    }
+    let superscript: String = exponent
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            d => "⁰¹²³⁴⁵⁶⁷⁸⁹".chars().nth(d.to_digit(10).unwrap_or(0) as usize).unwrap_or(d),
+        })
+        .collect();
+    format!("{mantissa:.1} × 10{superscript}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_answer, ParseOptions};
+    use proptest::prelude::*;
+
+    fn parse(input: &str) -> Result<f64, TestCaseError> {
+        parse_answer(input, ParseOptions::default())
+            .map(|answer| answer.bounds().0.get())
+            .map_err(|err| TestCaseError::fail(format!("{input:?} did not parse: {err}")))
+    }
+
+    proptest! {
+        #[test]
+        fn format_number_round_trips(exponent in 0.0..15.0f64, negative: bool) {
+            let x = if negative { -1.0 } else { 1.0 } * 10_f64.powf(exponent);
+            let text = format_number(x);
+            // One decimal place of the thousand/million/billion/trillion unit
+            let unit = [1e12, 1e9, 1e6, 1e3].into_iter().find(|&u| x.abs() >= u).unwrap_or(1.0);
+            let parsed = parse(&text)?;
+            prop_assert!((parsed - x).abs() <= 0.05 * unit + x.abs() * 1e-12, "{x} -> {text:?} -> {parsed}");
+        }
+
+        #[test]
+        fn format_scientific_round_trips(exponent in -12.0..15.0f64) {
+            let x = 10_f64.powf(exponent);
+            let text = format_scientific(x);
+            let parsed = parse(&text)?;
+            // One decimal place of the displayed mantissa
+            let place = 10_f64.powf((parsed.log10() + 1e-9).floor()) * 0.05;
+            prop_assert!((parsed - x).abs() <= place + x * 1e-12, "{x} -> {text:?} -> {parsed}");
+        }
+    }
+
+    #[test]
+    fn test_signed_challenges() {
+        let challenges = generate_challenges(42, 200, true);
+        assert!(challenges.iter().any(|c| c.answer() < 0.0));
+        assert!(challenges.iter().any(|c| c.num1 < 0.0 && c.num2 < 0.0 && c.answer() > 0.0));
+        assert!(challenges.iter().all(|c| c.answer() != 0.0));
+        assert!(generate_challenges(42, 200, false).iter().all(|c| c.num1 > 0.0 && c.num2 > 0.0));
+    }
+
+    #[test]
+    fn test_signs_keep_the_daily_problems() {
+        // Turning signs on only flips operands; the magnitudes are unchanged
+        let unsigned = generate_challenges(42, 50, false);
+        let signed = generate_challenges(42, 50, true);
+        for (u, s) in unsigned.iter().zip(&signed) {
+            assert_eq!((u.num1, u.num2, u.is_division), (s.num1.abs(), s.num2.abs(), s.is_division));
+        }
+        // Seed 42's first problem, as generated before signs existed
+        assert_eq!(unsigned[0], Challenge { num1: 2.4e4, num2: 4.9e9, is_division: false });
+    }
+
+    #[test]
+    fn test_format_scientific() {
+        assert_eq!(format_scientific(4e11), "4.0 × 10¹¹");
+        assert_eq!(format_scientific(8.3e6), "8.3 × 10⁶");
+        assert_eq!(format_scientific(9.96e5), "1.0 × 10⁶");
+        assert_eq!(format_scientific(4.5e-3), "4.5 × 10⁻³");
+        assert_eq!(format_scientific(-2e9), "-2.0 × 10⁹");
+    }
+}
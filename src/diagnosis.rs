@@ -0,0 +1,127 @@
+use crate::challenge::{Challenge, Operation};
+use crate::scoring::{log_error, Estimate};
+
+/// A recognizable slip behind a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mistake {
+    /// Worked out num2 / num1 instead of num1 / num2
+    InvertedDivision,
+    /// Multiplied on a division problem
+    MultipliedInstead,
+    /// Divided on a multiplication problem
+    DividedInstead,
+    /// Mantissas multiplied past 10 (or divided below 1) without moving the exponent
+    ForgotCarry,
+    /// Right digits, exponent off by a whole number of orders of magnitude
+    ZeroCount { orders: i32 },
+}
+
+impl Mistake {
+    pub fn feedback(self) -> String {
+        match self {
+            Self::InvertedDivision => "The division looks flipped: that's the second number over the first".into(),
+            Self::MultipliedInstead => "That's the product; this one was a division".into(),
+            Self::DividedInstead => "That's the quotient; this one was a multiplication".into(),
+            Self::ForgotCarry => "The mantissas crossed a power of ten; carry it into the exponent".into(),
+            Self::ZeroCount { orders } if orders.abs() == 3 => format!(
+                "Off by exactly {}: a thousand/million/billion mix-up?",
+                if orders > 0 { "a factor of 1000 too high" } else { "a factor of 1000 too low" }
+            ),
+            Self::ZeroCount { orders } => format!(
+                "Right digits, but {} {} too {}: recount the zeros",
+                orders.abs(),
+                if orders.abs() == 1 { "zero" } else { "zeros" },
+                if orders > 0 { "many" } else { "few" }
+            ),
+        }
+    }
+}
+
+/// How close, in OOM, an answer must be to a mistaken result to blame it
+const MATCH_TOLERANCE: f64 = 0.05;
+
+/// The most specific mistake that explains `answer`, if any. Hypotheses that
+/// would also give the right answer are skipped, since they explain nothing.
+pub fn diagnose(challenge: &Challenge, answer: Estimate) -> Option<Mistake> {
+    let correct = Estimate::new(challenge.answer()).ok()?;
+    let matches = |value: f64| {
+        Estimate::new(value).is_ok_and(|mistaken| {
+            log_error(mistaken, correct).abs() > MATCH_TOLERANCE
+                && log_error(answer, mistaken).abs() <= MATCH_TOLERANCE
+        })
+    };
+    let (num1, num2) = (challenge.num1, challenge.num2);
+    // Forgetting the carry leaves the answer a factor of ten too small, or
+    // forgetting the borrow a factor of ten too large
+    let uncarried = match challenge.operation() {
+        Operation::Multiply => correct.get() / 10.0,
+        Operation::Divide => correct.get() * 10.0,
+    };
+
+    let mistake = match challenge.operation() {
+        Operation::Divide if matches(num2 / num1) => Mistake::InvertedDivision,
+        Operation::Divide if matches(num1 * num2) => Mistake::MultipliedInstead,
+        Operation::Multiply if matches(num1 / num2) || matches(num2 / num1) => Mistake::DividedInstead,
+        _ if challenge.carries() && matches(uncarried) => Mistake::ForgotCarry,
+        _ => {
+            let error = log_error(answer, correct);
+            let orders = error.round();
+            if orders == 0.0 || orders.abs() > 3.0 || (error - orders).abs() > MATCH_TOLERANCE {
+                return None;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let orders = orders as i32;
+            Mistake::ZeroCount { orders }
+        }
+    };
+    Some(mistake)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnosis(num1: f64, num2: f64, is_division: bool, answer: f64) -> Option<Mistake> {
+        diagnose(&Challenge { num1, num2, is_division }, Estimate::new(answer).unwrap())
+    }
+
+    #[test]
+    fn test_operation_mistakes() {
+        assert_eq!(diagnosis(8e9, 2e3, true, 2.5e-7), Some(Mistake::InvertedDivision));
+        assert_eq!(diagnosis(8e9, 2e3, true, 1.6e13), Some(Mistake::MultipliedInstead));
+        assert_eq!(diagnosis(8e9, 2e3, false, 4e6), Some(Mistake::DividedInstead));
+    }
+
+    #[test]
+    fn test_forgot_carry() {
+        // 4 × 5 = 20, so 4e6 × 5e3 is 2e10, not 2e9
+        assert_eq!(diagnosis(4e6, 5e3, false, 2e9), Some(Mistake::ForgotCarry));
+        // 2 / 5 = 0.4, so 2e9 / 5e3 is 4e5, not 4e6
+        assert_eq!(diagnosis(2e9, 5e3, true, 4e6), Some(Mistake::ForgotCarry));
+        // Without a carry the same slip is a plain zero count
+        assert_eq!(diagnosis(2e6, 3e3, false, 6e8), Some(Mistake::ZeroCount { orders: -1 }));
+    }
+
+    #[test]
+    fn test_zero_count() {
+        assert_eq!(diagnosis(2e6, 3e3, false, 6e10), Some(Mistake::ZeroCount { orders: 1 }));
+        assert_eq!(diagnosis(2e6, 3e3, false, 6e12), Some(Mistake::ZeroCount { orders: 3 }));
+        // Four or more orders off is not a slip worth naming
+        assert_eq!(diagnosis(2e6, 3e3, false, 6e5), None);
+    }
+
+    #[test]
+    fn test_no_diagnosis() {
+        assert_eq!(diagnosis(2e6, 3e3, false, 6e9), None);
+        assert_eq!(diagnosis(2e6, 3e3, false, 2e10), None);
+        // A division by a number near 1 looks the same either way round
+        assert_eq!(diagnosis(3e3, 2.9e3, true, 3e3 / 2.9e3), None);
+    }
+
+    #[test]
+    fn test_feedback() {
+        assert!(Mistake::ZeroCount { orders: 3 }.feedback().contains("1000 too high"));
+        assert!(Mistake::ZeroCount { orders: -2 }.feedback().contains("2 zeros too few"));
+        assert!(Mistake::ZeroCount { orders: 1 }.feedback().contains("1 zero too many"));
+    }
+}
@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::challenge::Challenge;
+use crate::parser::Answer;
+use crate::scoring::{Attempt, Components, RuleKind, Score, ScoreResult};
+use crate::session::{attempt, ProblemResult};
+use crate::storage::{StorageBackend, StorageError};
+
+const HISTORY_KEY: &str = "oom-trainer-history";
+const HISTORY_BACKUP_KEY: &str = "oom-trainer-history-backup";
+/// Backup slots for unreadable histories, so repeated corruption can't fill
+/// the storage quota with copies
+const MAX_BACKUPS: usize = 3;
+
+/// Bump this and append to [`MIGRATIONS`] whenever the stored shape changes.
+/// New optional fields only need `#[serde(default)]`; renames, removals and
+/// new enum variants that older builds can't read need a migration.
+pub const HISTORY_VERSION: u32 = 1;
+
+/// `MIGRATIONS[i]` upgrades a document from version `i + 1` to `i + 2`.
+type Migration = fn(Value) -> Value;
+const MIGRATIONS: &[Migration] = &[];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryError {
+    /// Not JSON, or not a history document; worth keeping as a backup
+    Corrupt,
+    /// Written by a newer build; must be left untouched
+    TooNew(u32),
+}
+
+/// Every completed session, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub sessions: Vec<SessionRecord>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Local date, "YYYY-MM-DD"
+    pub date: String,
+    pub seed: u64,
+    pub problems: Vec<ProblemRecord>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProblemRecord {
+    pub challenge: Challenge,
+    pub answer: Answer,
+    pub result: ScoreResult,
+    pub points: u32,
+    pub rule: RuleKind,
+    /// Signed, in OOM; the distance is its absolute value
+    pub log_error: f64,
+    pub distance: f64,
+    pub seconds: f64,
+}
+
+impl From<&ProblemResult> for ProblemRecord {
+    fn from(result: &ProblemResult) -> Self {
+        Self {
+            challenge: result.challenge,
+            answer: result.answer,
+            result: result.score.result,
+            points: result.score.points,
+            rule: result.score.rule,
+            log_error: result.score.log_error,
+            distance: result.score.log_error.abs(),
+            seconds: result.seconds,
+        }
+    }
+}
+
+impl ProblemRecord {
+    pub fn attempt(&self) -> Option<Attempt> {
+        attempt(self.challenge, self.answer, self.seconds)
+    }
+
+    /// The live result this was recorded from, e.g. to show a finished day again.
+    pub fn to_result(self) -> Option<ProblemResult> {
+        let attempt = self.attempt()?;
+        let components = Components::new(attempt.estimate(), attempt.correct);
+        let score =
+            Score { result: self.result, points: self.points, rule: self.rule, log_error: self.log_error, components };
+        Some(ProblemResult { challenge: self.challenge, answer: self.answer, score, seconds: self.seconds })
+    }
+}
+
+impl History {
+    /// The recorded session for a day, if it was finished.
+    pub fn session(&self, date: &str, seed: u64) -> Option<&SessionRecord> {
+        self.sessions.iter().find(|s| s.date == date && s.seed == seed)
+    }
+
+    /// Add a finished session; a day already recorded keeps its first result.
+    pub fn record(&mut self, session: SessionRecord) -> bool {
+        if self.session(&session.date, session.seed).is_some() {
+            return false;
+        }
+        self.sessions.push(session);
+        true
+    }
+}
+
+/// The stored document: the version travels with the data.
+#[derive(Serialize)]
+struct Envelope<'a> {
+    version: u32,
+    history: &'a History,
+}
+
+/// Fails rather than produce a document [`decode`] would reject, e.g. one
+/// where a non-finite time was written as `null`.
+pub fn encode(history: &History) -> Result<String, StorageError> {
+    let envelope = Envelope { version: HISTORY_VERSION, history };
+    let json = serde_json::to_string(&envelope).map_err(|_| StorageError::Encoding)?;
+    decode(&json).map_err(|_| StorageError::Encoding)?;
+    Ok(json)
+}
+
+pub fn decode(json: &str) -> Result<History, HistoryError> {
+    migrate(json, MIGRATIONS)
+}
+
+/// Parse a stored document of any known version, upgrading it step by step.
+fn migrate(json: &str, migrations: &[Migration]) -> Result<History, HistoryError> {
+    let mut document: Value = serde_json::from_str(json).map_err(|_| HistoryError::Corrupt)?;
+    let version = document
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or(HistoryError::Corrupt)?;
+    let latest = u32::try_from(migrations.len()).map_or(u32::MAX, |n| n + 1);
+    if version > latest {
+        return Err(HistoryError::TooNew(version));
+    }
+    for migration in migrations.iter().skip(version.saturating_sub(1) as usize) {
+        document = migration(document);
+    }
+    let history = document.get_mut("history").map(Value::take).ok_or(HistoryError::Corrupt)?;
+    serde_json::from_value(history).map_err(|_| HistoryError::Corrupt)
+}
+
+/// The stored history for reading; anything unreadable shows as empty.
+pub fn stored(storage: &dyn StorageBackend) -> History {
+    storage.get(HISTORY_KEY).ok().flatten().and_then(|json| decode(&json).ok()).unwrap_or_default()
+}
+
+/// Save an unreadable history under the first free backup key: the plain key,
+/// then "-2", "-3" up to [`MAX_BACKUPS`]. Existing backups are never
+/// overwritten, and one already holding the same document is reused. With
+/// every slot taken this reports the storage as full.
+fn back_up(storage: &dyn StorageBackend, raw: &str) -> Result<(), StorageError> {
+    for n in 1..=MAX_BACKUPS {
+        let key = if n == 1 { HISTORY_BACKUP_KEY.to_string() } else { format!("{HISTORY_BACKUP_KEY}-{n}") };
+        match storage.get(&key)? {
+            None => return storage.set(&key, raw),
+            Some(existing) if existing == raw => return Ok(()),
+            Some(_) => {}
+        }
+    }
+    Err(StorageError::QuotaExceeded)
+}
+
+/// Append a finished session to the stored history, returning whether it was
+/// added. Stored data this build can't read is never overwritten: a newer
+/// build's history is left alone, and a corrupt one is backed up before
+/// starting over, or not replaced at all if the backup fails.
+pub fn append(storage: &dyn StorageBackend, session: SessionRecord) -> Result<bool, StorageError> {
+    let raw = storage.get(HISTORY_KEY)?;
+    let mut history = match raw.as_deref().map(decode) {
+        None => History::default(),
+        Some(Ok(history)) => history,
+        Some(Err(HistoryError::TooNew(_))) => return Ok(false),
+        Some(Err(HistoryError::Corrupt)) => {
+            back_up(storage, raw.as_deref().unwrap_or_default())?;
+            History::default()
+        }
+    };
+    if !history.record(session) {
+        return Ok(false);
+    }
+    storage.set(HISTORY_KEY, &encode(&history)?)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::Estimate;
+    use crate::storage::MemoryStorage;
+
+    fn problem() -> ProblemRecord {
+        ProblemRecord {
+            challenge: Challenge { num1: 2e6, num2: 3e3, is_division: false },
+            answer: Answer::Range { low: Estimate::new(4e9).unwrap(), high: Estimate::new(8e9).unwrap() },
+            result: ScoreResult::Exact,
+            points: 100,
+            rule: RuleKind::Banded,
+            log_error: -0.02,
+            distance: 0.02,
+            seconds: 7.5,
+        }
+    }
+
+    fn history() -> History {
+        History {
+            sessions: vec![SessionRecord { date: "2026-10-18".into(), seed: 42, problems: vec![problem(); 5] }],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let json = encode(&history()).unwrap();
+        assert!(json.starts_with(r#"{"version":1,"#));
+        assert_eq!(decode(&json), Ok(history()));
+    }
+
+    #[test]
+    fn test_empty_history() {
+        assert_eq!(decode(&encode(&History::default()).unwrap()), Ok(History::default()));
+    }
+
+    #[test]
+    fn test_unreadable_documents() {
+        assert_eq!(decode("not json"), Err(HistoryError::Corrupt));
+        assert_eq!(decode(r#"{"history":{"sessions":[]}}"#), Err(HistoryError::Corrupt));
+        assert_eq!(decode(r#"{"version":1,"history":{"sessions":[{"date":1}]}}"#), Err(HistoryError::Corrupt));
+        // A zero estimate can't have been written by this app
+        let json = encode(&history()).unwrap().replace("4000000000.0", "0.0");
+        assert_eq!(decode(&json), Err(HistoryError::Corrupt));
+    }
+
+    #[test]
+    fn test_newer_version_is_refused() {
+        let json = encode(&history()).unwrap().replace(r#""version":1"#, r#""version":7"#);
+        assert_eq!(decode(&json), Err(HistoryError::TooNew(7)));
+    }
+
+    #[test]
+    fn test_unknown_fields_are_ignored() {
+        let json = encode(&history()).unwrap().replace(r#""seed":42"#, r#""seed":42,"units":"usd""#);
+        assert_eq!(decode(&json), Ok(history()));
+    }
+
+    #[test]
+    fn test_migrations_run_in_order() {
+        // A hypothetical v2 that renamed "seconds" and a v3 that renamed it back
+        fn rename_seconds(mut doc: Value) -> Value {
+            let json = doc.to_string().replace(r#""seconds""#, r#""secs""#);
+            doc = serde_json::from_str(&json).unwrap();
+            doc["version"] = 2.into();
+            doc
+        }
+        fn restore_seconds(mut doc: Value) -> Value {
+            let json = doc.to_string().replace(r#""secs""#, r#""seconds""#);
+            doc = serde_json::from_str(&json).unwrap();
+            doc["version"] = 3.into();
+            doc
+        }
+        let migrations: &[Migration] = &[rename_seconds, restore_seconds];
+        let v1 = encode(&history()).unwrap();
+        assert_eq!(migrate(&v1, migrations), Ok(history()));
+        // A v2 document only runs the second step
+        let v2 = rename_seconds(serde_json::from_str(&v1).unwrap()).to_string();
+        assert_eq!(migrate(&v2, migrations), Ok(history()));
+        assert_eq!(migrate(&v2, &[]), Err(HistoryError::TooNew(2)));
+    }
+
+    #[test]
+    fn test_record_locks_a_day() {
+        let mut log = History::default();
+        let session = history().sessions[0].clone();
+        assert!(log.record(session.clone()));
+        let replay = SessionRecord { problems: vec![], ..session };
+        assert!(!log.record(replay));
+        assert_eq!(log.sessions[0].problems.len(), 5);
+        assert!(log.session("2026-10-18", 42).is_some());
+        assert!(log.session("2026-10-18", 43).is_none());
+    }
+
+    #[test]
+    fn test_append() {
+        let storage = MemoryStorage::default();
+        let session = history().sessions[0].clone();
+        assert_eq!(append(&storage, session.clone()), Ok(true));
+        assert_eq!(append(&storage, session.clone()), Ok(false));
+        let tomorrow = SessionRecord { date: "2026-10-19".into(), seed: 43, ..session };
+        assert_eq!(append(&storage, tomorrow), Ok(true));
+        assert_eq!(stored(&storage).sessions.len(), 2);
+    }
+
+    #[test]
+    fn test_append_keeps_unreadable_history() {
+        let session = history().sessions[0].clone();
+
+        let storage = MemoryStorage::default();
+        storage.set(HISTORY_KEY, "garbled").unwrap();
+        assert_eq!(append(&storage, session.clone()), Ok(true));
+        assert_eq!(storage.get(HISTORY_BACKUP_KEY), Ok(Some("garbled".into())));
+
+        // A second corruption gets a backup of its own
+        storage.set(HISTORY_KEY, "mangled").unwrap();
+        let tomorrow = SessionRecord { date: "2026-10-19".into(), ..session.clone() };
+        assert_eq!(append(&storage, tomorrow), Ok(true));
+        assert_eq!(storage.get(HISTORY_BACKUP_KEY), Ok(Some("garbled".into())));
+        assert_eq!(storage.get(&format!("{HISTORY_BACKUP_KEY}-2")), Ok(Some("mangled".into())));
+
+        let newer = encode(&History::default()).unwrap().replace(r#""version":1"#, r#""version":9"#);
+        storage.set(HISTORY_KEY, &newer).unwrap();
+        assert_eq!(append(&storage, session), Ok(false));
+        assert_eq!(storage.get(HISTORY_KEY), Ok(Some(newer)));
+        assert_eq!(stored(&storage), History::default());
+    }
+
+    #[test]
+    fn test_unencodable_history_is_not_written() {
+        let mut broken = history();
+        broken.sessions[0].problems[0].seconds = f64::NAN;
+        assert_eq!(encode(&broken), Err(StorageError::Encoding));
+
+        let storage = MemoryStorage::default();
+        let session = history().sessions[0].clone();
+        assert_eq!(append(&storage, session), Ok(true));
+        let before = storage.get(HISTORY_KEY).unwrap();
+        let tomorrow = SessionRecord { date: "2026-10-19".into(), ..broken.sessions[0].clone() };
+        assert_eq!(append(&storage, tomorrow), Err(StorageError::Encoding));
+        assert_eq!(storage.get(HISTORY_KEY).unwrap(), before);
+    }
+
+    #[test]
+    fn test_backup_slots_are_limited() {
+        let storage = MemoryStorage::default();
+        let session = history().sessions[0].clone();
+        for n in 0..MAX_BACKUPS {
+            storage.set(HISTORY_KEY, &format!("garbled {n}")).unwrap();
+            let day = SessionRecord { date: format!("2026-10-{}", 10 + n), ..session.clone() };
+            assert_eq!(append(&storage, day), Ok(true));
+        }
+        storage.set(HISTORY_KEY, "garbled again").unwrap();
+        assert_eq!(append(&storage, session), Err(StorageError::QuotaExceeded));
+        assert_eq!(storage.get(HISTORY_KEY), Ok(Some("garbled again".into())));
+        let last_backup = storage.get(&format!("{HISTORY_BACKUP_KEY}-{MAX_BACKUPS}"));
+        assert_eq!(last_backup, Ok(Some(format!("garbled {}", MAX_BACKUPS - 1))));
+    }
+
+    #[test]
+    fn test_corrupt_history_is_kept_when_backup_fails() {
+        let storage = MemoryStorage::with_quota(60);
+        storage.set(HISTORY_KEY, "garbled").unwrap();
+        storage.set(HISTORY_BACKUP_KEY, "older").unwrap();
+        let session = history().sessions[0].clone();
+        assert_eq!(append(&storage, session), Err(StorageError::QuotaExceeded));
+        assert_eq!(storage.get(HISTORY_KEY), Ok(Some("garbled".into())));
+        assert_eq!(storage.get(HISTORY_BACKUP_KEY), Ok(Some("older".into())));
+    }
+
+    #[test]
+    fn test_append_when_full() {
+        let storage = MemoryStorage::with_quota(100);
+        let session = history().sessions[0].clone();
+        assert_eq!(append(&storage, session), Err(StorageError::QuotaExceeded));
+        assert_eq!(storage.get(HISTORY_KEY), Ok(None));
+    }
+}
@@ -1,7 +1,13 @@
 mod app;
 mod challenge;
+mod diagnosis;
+mod history;
 mod parser;
+mod rating;
 mod scoring;
+mod session;
+mod stats;
+mod storage;
 
 use app::App;
 use leptos::{mount_to_body, view};
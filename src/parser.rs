@@ -1,143 +1,909 @@
-/// Parse user input into a numeric value
-/// Accepts formats like:
-/// - "400 billion", "400B", "400b"
-/// - "4e11", "4E11"
-/// - "4 × 10^11", "4 * 10^11", "4x10^11"
-/// - Plain numbers: "400000000000"
-pub fn parse_answer(input: &str) -> Option<f64> {
-    let input = input.trim().to_lowercase();
+mod lexer;
+mod words;
 
-    if input.is_empty() {
-        return None;
-    }
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+
+use crate::scoring::{Estimate, EstimateError};
+use lexer::{tokenize, Token, TokenKind};
+use words::spelled_number;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Empty,
+    InvalidNumber,
+    UnknownSuffix,
+    MalformedExponent,
+    TrailingGarbage,
+    OutOfRange,
+    Zero,
+    RangeCrossesZero,
+}
 
-    // Try scientific notation first (4e11, 4E11)
-    if let Some(val) = parse_scientific(&input) {
-        return Some(val);
+impl From<EstimateError> for ParseErrorKind {
+    fn from(err: EstimateError) -> Self {
+        match err {
+            EstimateError::NotFinite => Self::OutOfRange,
+            EstimateError::Zero => Self::Zero,
+        }
     }
+}
 
-    // Try word suffixes (million, billion, etc.)
-    if let Some(val) = parse_word_suffix(&input) {
-        return Some(val);
+/// Why an answer was rejected, with the byte range of the offending text
+/// in the original (untrimmed) input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Range<usize>,
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, span: Range<usize>) -> Self {
+        Self { kind, span }
     }
 
-    // Try letter suffixes (K, M, B, T)
-    if let Some(val) = parse_letter_suffix(&input) {
-        return Some(val);
+    pub fn message(&self) -> &'static str {
+        match self.kind {
+            ParseErrorKind::Empty => "enter an estimate",
+            ParseErrorKind::InvalidNumber => "expected a number",
+            ParseErrorKind::UnknownSuffix => "unknown suffix (try K, M, B, T or a word like billion)",
+            ParseErrorKind::MalformedExponent => "malformed exponent (expected e.g. e11 or x10^11)",
+            ParseErrorKind::TrailingGarbage => "unexpected text after the number",
+            ParseErrorKind::OutOfRange => "number is too large to work with",
+            ParseErrorKind::Zero => "estimate can't be zero",
+            ParseErrorKind::RangeCrossesZero => "a range can't run from negative to positive",
+        }
     }
+}
 
-    // Try caret notation (4 × 10^11)
-    if let Some(val) = parse_caret_notation(&input) {
-        return Some(val);
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.message(), self.span.start, self.span.end)
     }
+}
+
+/// How the typed text should be read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnswerMode {
+    /// A full value such as "400B" or "4e11"
+    #[default]
+    Value,
+    /// Just the order of magnitude: "11" means 10^11, "11.6" means 10^11.6
+    Exponent,
+}
+
+/// How single-letter suffixes are read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SuffixStyle {
+    /// "5m" and "5M" are both five million
+    #[default]
+    Finance,
+    /// Single letters are case-sensitive SI prefixes: "5m" is 0.005, "5M" is 5 million
+    Physics,
+}
 
-    // Try plain number
-    input.replace([',', ' '], "").parse().ok()
+/// Which character marks the decimal part; the other one groups thousands.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecimalSeparator {
+    /// "1,000,000.5"
+    #[default]
+    Point,
+    /// "1.000.000,5"
+    Comma,
 }
 
-fn parse_scientific(input: &str) -> Option<f64> {
-    // Handle 4e11, 4E11 format
-    if input.contains('e') {
-        return input.parse().ok();
+impl DecimalSeparator {
+    /// Pick the separator for a BCP 47 language tag such as "de-DE" or "en-US".
+    pub fn for_language(tag: &str) -> Self {
+        const COMMA_LANGUAGES: [&str; 22] = [
+            "bg", "ca", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "it",
+            "lt", "nb", "nl", "pl", "pt", "ru", "sv", "tr",
+        ];
+        // Regions where a comma language writes 1,000,000.5 or 1'000'000.5 instead
+        const POINT_REGIONS: [(&str, &[&str]); 3] = [
+            ("de", &["ch", "li"]),
+            ("it", &["ch"]),
+            ("es", &["419", "cu", "do", "gt", "hn", "mx", "ni", "pa", "pe", "pr", "sv", "us"]),
+        ];
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+        // The region is the first two-letter or three-digit subtag, after any script
+        let region = parts
+            .find(|part| part.len() == 2 || (part.len() == 3 && part.bytes().all(|b| b.is_ascii_digit())))
+            .map(str::to_ascii_lowercase);
+        let point_region = POINT_REGIONS
+            .iter()
+            .any(|(lang, regions)| *lang == language && region.as_deref().is_some_and(|r| regions.contains(&r)));
+        if COMMA_LANGUAGES.contains(&language.as_str()) && !point_region {
+            Self::Comma
+        } else {
+            Self::Point
+        }
     }
-    None
+
+    fn decimal_char(self) -> char {
+        match self {
+            Self::Point => '.',
+            Self::Comma => ',',
+        }
+    }
+
+    /// Spaces (including thin and no-break spaces) and apostrophes group
+    /// digits in every locale, as does whichever of '.' and ',' is not the decimal mark.
+    fn is_group_char(self, c: char) -> bool {
+        let other = match self {
+            Self::Point => ',',
+            Self::Comma => '.',
+        };
+        c == other || matches!(c, ' ' | '\'' | '’' | '\u{2009}' | '\u{202F}' | '\u{A0}')
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub mode: AnswerMode,
+    pub suffixes: SuffixStyle,
+    pub decimal: DecimalSeparator,
 }
 
-fn parse_word_suffix(input: &str) -> Option<f64> {
-    let suffixes = [
-        ("trillion", 1e12),
-        ("billion", 1e9),
-        ("million", 1e6),
-        ("thousand", 1e3),
-    ];
+/// A parsed estimate: a single value, or a range such as "300-500B".
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Answer {
+    Point(Estimate),
+    Range { low: Estimate, high: Estimate },
+}
 
-    for (suffix, multiplier) in suffixes {
-        if input.ends_with(suffix) {
-            let num_part = input.strip_suffix(suffix)?.trim();
-            let num: f64 = num_part.parse().ok()?;
-            return Some(num * multiplier);
+impl Answer {
+    pub fn bounds(self) -> (Estimate, Estimate) {
+        match self {
+            Self::Point(value) => (value, value),
+            Self::Range { low, high } => (low, high),
         }
     }
-    None
 }
 
-fn parse_letter_suffix(input: &str) -> Option<f64> {
-    let last_char = input.chars().last()?;
-    let multiplier = match last_char {
-        't' => 1e12,
-        'b' => 1e9,
-        'm' => 1e6,
-        'k' => 1e3,
-        _ => return None,
-    };
+/// Parse user input into an answer
+/// Accepts formats like:
+/// - "400 billion", "400B", "400b"
+/// - "4e11", "4E11"
+/// - "4 × 10^11", "4 * 10^11", "4x10^11", "4·10¹¹", "10**11", "10⁻³"
+/// - Plain numbers: "400000000000", "400,000,000,000", "400'000'000'000"
+/// - Comma decimals when [`DecimalSeparator::Comma`] is set: "4,5 Mio", "1.000.000"
+/// - Hedges and ranges: "~400B", "about 400 billion", "300–500B", "3e11 to 5e11"
+/// - An optional currency or unit: "$400B", "400 billion dollars"
+/// - Spelled-out numbers: "four hundred billion", "half a trillion"
+/// - SI prefixes and finance shorthand: "4.5 giga", "4.5G", "4.5bn", "3 lakh"
+///
+/// The grammar is `[approx] quantity [separator [approx] quantity]`, where a
+/// quantity is `[currency] [sign] number multiplier* [× power] [unit]` or
+/// `[currency] [sign] power [unit]`, and a power is `10 ^ [sign] number` or
+/// `10` with a superscript exponent.
+/// In [`AnswerMode::Exponent`] it is just `[approx] [[-] 10 ^] [sign] number`,
+/// where only a sign before `10 ^` makes the value negative.
+pub fn parse_answer(input: &str, options: ParseOptions) -> Result<Answer, ParseError> {
+    let tokens = tokenize(input, options.decimal)?;
+    if tokens.is_empty() {
+        return Err(ParseError::new(ParseErrorKind::Empty, 0..input.len()));
+    }
+    let grammar = Grammar { tokens: &tokens, pos: 0, input, options };
+    let (low, high) = match options.mode {
+        AnswerMode::Value => grammar.answer(),
+        AnswerMode::Exponent => grammar.exponent(),
+    }?;
+
+    // Catches overflow ("1e999"), NaN ("0 x 10^999") and zero
+    let span = tokens[0].span.start..tokens[tokens.len() - 1].span.end;
+    let estimate = |value| Estimate::new(value).map_err(|err| ParseError::new(err.into(), span.clone()));
+    let low = estimate(low)?;
+    if low.get() == high {
+        return Ok(Answer::Point(low));
+    }
+    let high = estimate(high)?;
+    if low.is_negative() != high.is_negative() {
+        return Err(ParseError::new(ParseErrorKind::RangeCrossesZero, span));
+    }
+    Ok(Answer::Range { low, high })
+}
+
+/// Scale words, matched case-insensitively in every style.
+const WORD_SUFFIXES: [(&str, f64); 10] = [
+    ("trillion", 1e12),
+    ("billion", 1e9),
+    ("million", 1e6),
+    ("thousand", 1e3),
+    ("hundred", 1e2),
+    ("crore", 1e7),
+    ("crores", 1e7),
+    ("lakh", 1e5),
+    ("lakhs", 1e5),
+    ("lac", 1e5),
+];
+
+const SI_WORDS: [(&str, f64); 10] = [
+    ("exa", 1e18),
+    ("peta", 1e15),
+    ("tera", 1e12),
+    ("giga", 1e9),
+    ("mega", 1e6),
+    ("kilo", 1e3),
+    ("milli", 1e-3),
+    ("micro", 1e-6),
+    ("nano", 1e-9),
+    ("pico", 1e-12),
+];
+
+/// Finance shorthand, matched case-insensitively.
+const FINANCE_ABBREVIATIONS: [(&str, f64); 12] = [
+    ("tn", 1e12),
+    ("t", 1e12),
+    ("bn", 1e9),
+    ("mrd", 1e9),
+    ("b", 1e9),
+    ("mm", 1e6),
+    ("mn", 1e6),
+    ("mio", 1e6),
+    ("m", 1e6),
+    ("thou", 1e3),
+    ("tsd", 1e3),
+    ("k", 1e3),
+];
+
+/// SI prefix symbols, matched case-sensitively.
+const SI_SYMBOLS: [(&str, f64); 12] = [
+    ("E", 1e18),
+    ("P", 1e15),
+    ("T", 1e12),
+    ("G", 1e9),
+    ("M", 1e6),
+    ("k", 1e3),
+    ("m", 1e-3),
+    ("µ", 1e-6),
+    ("μ", 1e-6),
+    ("u", 1e-6),
+    ("n", 1e-9),
+    ("p", 1e-12),
+];
+
+fn lookup(table: &[(&str, f64)], key: &str) -> Option<f64> {
+    table.iter().find(|&&(suffix, _)| suffix == key).map(|&(_, multiplier)| multiplier)
+}
+
+/// Resolve a suffix, given both its lowercased and its typed form.
+fn suffix_multiplier(word: &str, raw: &str, style: SuffixStyle) -> Option<f64> {
+    lookup(&WORD_SUFFIXES, word).or_else(|| lookup(&SI_WORDS, word)).or_else(|| match style {
+        SuffixStyle::Finance => {
+            lookup(&FINANCE_ABBREVIATIONS, word).or_else(|| lookup(&SI_SYMBOLS, raw))
+        }
+        SuffixStyle::Physics => lookup(&SI_SYMBOLS, raw)
+            .or_else(|| lookup(&FINANCE_ABBREVIATIONS, word).filter(|_| word.chars().count() > 1)),
+    })
+}
+
+const APPROXIMATELY: [&str; 7] = ["about", "around", "approx", "approximately", "roughly", "circa", "ca"];
 
-    // Make sure it's not just a word ending in these letters
-    let num_part = &input[..input.len() - 1].trim();
-    if num_part.is_empty() {
-        return None;
+const UNITS: [&str; 6] = ["dollars", "usd", "euros", "eur", "pounds", "gbp"];
+
+struct Grammar<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    input: &'a str,
+    options: ParseOptions,
+}
+
+/// One side of an answer: its value, and the scale applied by any
+/// multiplier words or power of ten, if there were any.
+struct Quantity {
+    value: f64,
+    scale: Option<f64>,
+    unit: bool,
+}
+
+impl Quantity {
+    fn plain(value: f64) -> Self {
+        Self { value, scale: None, unit: false }
+    }
+
+    fn scale_by(&mut self, factor: f64) {
+        self.value *= factor;
+        self.scale = Some(self.scale.unwrap_or(1.0) * factor);
     }
 
-    // Check if the part before is actually a number
-    let num: f64 = num_part.parse().ok()?;
-    Some(num * multiplier)
+    fn suffixed(&self) -> bool {
+        self.scale.is_some() || self.unit
+    }
 }
 
-fn parse_caret_notation(input: &str) -> Option<f64> {
-    // Handle formats like "4 × 10^11", "4 * 10^11", "4x10^11", "4 x 10^11"
-    let input = input
-        .replace(['×', '*'], "x")
-        .replace(' ', "");
+impl Grammar<'_> {
+    /// `[approx] quantity [separator [approx] quantity]`, as the ordered
+    /// ends of a range; a single value is returned as both ends.
+    fn answer(mut self) -> Result<(f64, f64), ParseError> {
+        self.approximately();
+        let low = self.quantity()?;
+        if !self.range_separator() {
+            self.finish(low.suffixed())?;
+            return Ok((low.value, low.value));
+        }
+
+        self.approximately();
+        let high = self.quantity()?;
+        self.finish(high.suffixed())?;
 
-    if !input.contains("x10^") {
-        return None;
+        // "300-500B": a bare lower bound takes the upper bound's scale
+        let low = match (low.scale, high.scale) {
+            (None, Some(scale)) => low.value * scale,
+            _ => low.value,
+        };
+        Ok(if low <= high.value { (low, high.value) } else { (high.value, low) })
     }
 
-    let parts: Vec<&str> = input.split("x10^").collect();
-    if parts.len() != 2 {
-        return None;
+    fn quantity(&mut self) -> Result<Quantity, ParseError> {
+        // The sign may come either side of the currency: "-$5" or "$-5"
+        let mut sign = self.sign();
+        if self.eat(&TokenKind::Currency) && sign > 0.0 {
+            sign = self.sign();
+        }
+        // A bare power of ten such as "10^11" or "10⁻³" has no mantissa
+        if self.at_power_of_ten() {
+            let mut quantity = Quantity::plain(sign);
+            quantity.scale_by(self.power_of_ten()?);
+            quantity.unit = self.unit();
+            return Ok(quantity);
+        }
+        let mut quantity = self.number()?;
+        quantity.value *= sign;
+
+        if let Some(exponent) = self.detached_exponent() {
+            quantity.scale_by(exponent?);
+        }
+
+        while let Some(multiplier) = self.multiplier() {
+            quantity.scale_by(multiplier);
+        }
+
+        if self.eat(&TokenKind::Times) {
+            if !self.at_power_of_ten() {
+                let start = self.tokens[self.pos - 1].span.start;
+                let end = self.peek().map_or(self.input.len(), |t| t.span.end);
+                return Err(ParseError::new(ParseErrorKind::MalformedExponent, start..end));
+            }
+            quantity.scale_by(self.power_of_ten()?);
+        }
+
+        quantity.unit = self.unit();
+        Ok(quantity)
     }
 
-    let mantissa: f64 = parts[0].parse().ok()?;
-    let exponent: i32 = parts[1].parse().ok()?;
+    /// `[10 ^] [sign] number`, read as a power of ten.
+    fn exponent(mut self) -> Result<(f64, f64), ParseError> {
+        self.approximately();
+        // "10^" at `pos`, typed out of habit or to carry a value sign
+        let base_at = |pos: usize| {
+            matches!(
+                self.tokens.get(pos),
+                Some(Token { kind: TokenKind::Number { value, integer: true }, .. }) if *value == 10.0
+            ) && self.tokens.get(pos + 1).is_some_and(|t| t.kind == TokenKind::Caret)
+        };
+        // A sign before "10^" belongs to the value; before a bare exponent
+        // ("-3") it stays the exponent's
+        let negative = self.peek().is_some_and(|t| t.kind == TokenKind::Minus) && base_at(self.pos + 1);
+        if negative {
+            self.pos += 1;
+        }
+        if base_at(self.pos) {
+            self.pos += 2;
+        }
+        let sign = self.sign();
+        let exponent = sign * self.number()?.value;
+        self.finish(true)?;
+        let value = 10_f64.powf(exponent);
+        let value = if negative { -value } else { value };
+        Ok((value, value))
+    }
 
-    Some(mantissa * 10_f64.powi(exponent))
+    /// Skip hedges such as "~", "≈" or "about".
+    fn approximately(&mut self) {
+        match self.peek().map(|t| &t.kind) {
+            Some(TokenKind::Approx) => self.pos += 1,
+            Some(TokenKind::Word(word)) if APPROXIMATELY.contains(&word.as_str()) => {
+                self.pos += 1;
+                // "ca." and "approx."
+                self.eat(&TokenKind::Period);
+            }
+            _ => {}
+        }
+    }
+
+    /// "-", "–", "—" or "to" between the two ends of a range.
+    fn range_separator(&mut self) -> bool {
+        let is_separator = match self.peek().map(|t| &t.kind) {
+            Some(TokenKind::Minus | TokenKind::Dash) => true,
+            Some(TokenKind::Word(word)) => word == "to",
+            _ => false,
+        };
+        if is_separator {
+            self.pos += 1;
+        }
+        is_separator
+    }
+
+    /// Succeed if every token was consumed, allowing the full stop that
+    /// voice dictation puts at the end of a sentence.
+    fn finish(&mut self, suffixed: bool) -> Result<(), ParseError> {
+        if self.pos + 1 == self.tokens.len() {
+            self.eat(&TokenKind::Period);
+        }
+        match self.peek() {
+            None => Ok(()),
+            Some(Token { kind: TokenKind::Word(word), span }) if word == "e" => {
+                Err(ParseError::new(ParseErrorKind::MalformedExponent, span.clone()))
+            }
+            Some(Token { kind: TokenKind::Word(_), span }) if !suffixed => {
+                Err(ParseError::new(ParseErrorKind::UnknownSuffix, span.clone()))
+            }
+            Some(token) => Err(ParseError::new(
+                ParseErrorKind::TrailingGarbage,
+                token.span.start..self.input.len(),
+            )),
+        }
+    }
+
+    fn number(&mut self) -> Result<Quantity, ParseError> {
+        match self.peek() {
+            Some(&Token { kind: TokenKind::Number { value, .. }, .. }) => {
+                self.pos += 1;
+                Ok(Quantity::plain(value))
+            }
+            Some(token @ Token { kind: TokenKind::Word(_), .. }) => {
+                let spelled = spelled_number(&self.tokens[self.pos..])
+                    .ok_or_else(|| ParseError::new(ParseErrorKind::InvalidNumber, token.span.clone()))?;
+                self.pos += spelled.len;
+                Ok(Quantity { value: spelled.value, scale: spelled.scale, unit: false })
+            }
+            Some(token) => Err(ParseError::new(ParseErrorKind::InvalidNumber, token.span.clone())),
+            None => Err(ParseError::new(ParseErrorKind::InvalidNumber, self.input.len()..self.input.len())),
+        }
+    }
+
+    fn multiplier(&mut self) -> Option<f64> {
+        let Some(Token { kind: TokenKind::Word(word), span }) = self.peek() else {
+            return None;
+        };
+        let multiplier = suffix_multiplier(word, &self.input[span.clone()], self.options.suffixes)?;
+        self.pos += 1;
+        Some(multiplier)
+    }
+
+    /// Whether the next tokens are `10` followed by `^`, `**` or a superscript.
+    fn at_power_of_ten(&self) -> bool {
+        let is_ten = matches!(
+            self.peek(),
+            Some(Token { kind: TokenKind::Number { value, integer: true }, .. }) if *value == 10.0
+        );
+        is_ten
+            && matches!(
+                self.tokens.get(self.pos + 1).map(|t| &t.kind),
+                Some(TokenKind::Caret | TokenKind::Superscript(_))
+            )
+    }
+
+    /// `10 ^ [sign] number` or `10` with a superscript exponent.
+    fn power_of_ten(&mut self) -> Result<f64, ParseError> {
+        let start = self.tokens[self.pos].span.start;
+        self.pos += 1;
+        if let Some(&Token { kind: TokenKind::Superscript(exponent), .. }) = self.peek() {
+            self.pos += 1;
+            return Ok(10_f64.powf(exponent));
+        }
+        self.pos += 1;
+        let sign = self.sign();
+        if let Some(&Token { kind: TokenKind::Number { value, .. }, .. }) = self.peek() {
+            self.pos += 1;
+            return Ok(10_f64.powf(sign * value));
+        }
+        let end = self.peek().map_or(self.input.len(), |t| t.span.end);
+        Err(ParseError::new(ParseErrorKind::MalformedExponent, start..end))
+    }
+
+    /// A spreadsheet-style exponent typed apart from the mantissa: "4 E+11".
+    fn detached_exponent(&mut self) -> Option<Result<f64, ParseError>> {
+        let Some(Token { kind: TokenKind::Word(word), span }) = self.peek() else {
+            return None;
+        };
+        let signed_or_number = matches!(
+            self.tokens.get(self.pos + 1).map(|t| &t.kind),
+            Some(TokenKind::Plus | TokenKind::Minus | TokenKind::Number { .. })
+        );
+        // Otherwise "E" may be the exa prefix
+        if word != "e" || !signed_or_number {
+            return None;
+        }
+        let start = span.start;
+        self.pos += 1;
+        let sign = self.sign();
+        if let Some(&Token { kind: TokenKind::Number { value, integer: true }, .. }) = self.peek() {
+            self.pos += 1;
+            return Some(Ok(10_f64.powf(sign * value)));
+        }
+        let end = self.peek().map_or(self.input.len(), |t| t.span.end);
+        Some(Err(ParseError::new(ParseErrorKind::MalformedExponent, start..end)))
+    }
+
+    fn unit(&mut self) -> bool {
+        match self.peek() {
+            Some(Token { kind: TokenKind::Word(word), .. }) if UNITS.contains(&word.as_str()) => {
+                self.pos += 1;
+                true
+            }
+            _ => self.eat(&TokenKind::Currency),
+        }
+    }
+
+    fn sign(&mut self) -> f64 {
+        if self.eat(&TokenKind::Minus) {
+            -1.0
+        } else {
+            self.eat(&TokenKind::Plus);
+            1.0
+        }
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        let matched = self.peek().is_some_and(|t| &t.kind == kind);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    fn parse(input: &str) -> Result<f64, ParseError> {
+        parse_answer(input, ParseOptions::default()).map(value)
+    }
+
+    fn value(answer: Answer) -> f64 {
+        match answer {
+            Answer::Point(value) => value.get(),
+            Answer::Range { low, high } => low.geometric_mean(high).get(),
+        }
+    }
+
+    fn bounds(answer: Answer) -> (f64, f64) {
+        let (low, high) = answer.bounds();
+        (low.get(), high.get())
+    }
 
     #[test]
     fn test_scientific() {
-        assert_eq!(parse_answer("4e11"), Some(4e11));
-        assert_eq!(parse_answer("4E11"), Some(4e11));
-        assert_eq!(parse_answer("3.5e6"), Some(3.5e6));
+        assert_eq!(parse("4e11"), Ok(4e11));
+        assert_eq!(parse("4E11"), Ok(4e11));
+        assert_eq!(parse("3.5e6"), Ok(3.5e6));
     }
 
     #[test]
     fn test_word_suffix() {
-        assert_eq!(parse_answer("400 billion"), Some(400e9));
-        assert_eq!(parse_answer("3.5 million"), Some(3.5e6));
-        assert_eq!(parse_answer("50 thousand"), Some(50e3));
+        assert_eq!(parse("400 billion"), Ok(400e9));
+        assert_eq!(parse("3.5 million"), Ok(3.5e6));
+        assert_eq!(parse("50 thousand"), Ok(50e3));
     }
 
     #[test]
     fn test_letter_suffix() {
-        assert_eq!(parse_answer("400B"), Some(400e9));
-        assert_eq!(parse_answer("3.5M"), Some(3.5e6));
-        assert_eq!(parse_answer("50K"), Some(50e3));
+        assert_eq!(parse("400B"), Ok(400e9));
+        assert_eq!(parse("3.5M"), Ok(3.5e6));
+        assert_eq!(parse("50K"), Ok(50e3));
     }
 
     #[test]
     fn test_caret_notation() {
-        assert_eq!(parse_answer("4 × 10^11"), Some(4e11));
-        assert_eq!(parse_answer("4 * 10^11"), Some(4e11));
-        assert_eq!(parse_answer("4x10^11"), Some(4e11));
+        assert_eq!(parse("4 × 10^11"), Ok(4e11));
+        assert_eq!(parse("4 * 10^11"), Ok(4e11));
+        assert_eq!(parse("4x10^11"), Ok(4e11));
     }
 
     #[test]
     fn test_plain_number() {
-        assert_eq!(parse_answer("400000000000"), Some(400000000000.0));
-        assert_eq!(parse_answer("400,000,000,000"), Some(400000000000.0));
+        assert_eq!(parse("400000000000"), Ok(400000000000.0));
+        assert_eq!(parse("400,000,000,000"), Ok(400000000000.0));
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |s| parse(s).unwrap_err().kind;
+        assert_eq!(kind(""), ParseErrorKind::Empty);
+        assert_eq!(kind("   "), ParseErrorKind::Empty);
+        assert_eq!(kind("lots"), ParseErrorKind::InvalidNumber);
+        assert_eq!(kind("4e"), ParseErrorKind::MalformedExponent);
+        assert_eq!(kind("4 x 10^"), ParseErrorKind::MalformedExponent);
+        assert_eq!(kind("400B please"), ParseErrorKind::TrailingGarbage);
+    }
+
+    #[test]
+    fn test_error_spans() {
+        assert_eq!(
+            parse("4.5zn"),
+            Err(ParseError { kind: ParseErrorKind::UnknownSuffix, span: 3..5 })
+        );
+        assert_eq!(
+            parse("  400 zillion"),
+            Err(ParseError { kind: ParseErrorKind::UnknownSuffix, span: 6..13 })
+        );
+        assert_eq!(parse("4e1x1").unwrap_err().span, 3..5);
+    }
+
+    #[test]
+    fn test_grammar_precedence() {
+        // An attached 'e' is an exponent; a word starting with 'e' is not
+        assert_eq!(parse("4e11 dollars"), Ok(4e11));
+        assert_eq!(parse("$400B"), Ok(400e9));
+        assert_eq!(parse("4 thousand million"), Ok(4e9));
+        assert_eq!(parse("4.5 × 10^-3"), Ok(4.5 * 10_f64.powf(-3.0)));
+        assert_eq!(parse("400 000"), Ok(400_000.0));
+        assert_eq!(parse("4 eggs").unwrap_err().kind, ParseErrorKind::UnknownSuffix);
+    }
+
+    #[test]
+    fn test_multibyte_input() {
+        assert_eq!(parse("4é").unwrap_err().kind, ParseErrorKind::UnknownSuffix);
+        assert_eq!(parse("é").unwrap_err().kind, ParseErrorKind::InvalidNumber);
+        assert_eq!(parse("4ü5").unwrap_err().span, 1..3);
+        assert_eq!(parse("4 🙂").unwrap_err().span, 2..6);
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics(input in any::<String>()) {
+            let _ = parse(&input);
+        }
+
+        #[test]
+        fn never_panics_on_answer_like_input(input in "[0-9.,eE+\\-xX×*^ kmbtµé€$]{0,16}") {
+            let _ = parse(&input);
+        }
+
+        #[test]
+        fn never_returns_non_finite(input in any::<String>()) {
+            if let Ok(answer) = parse_answer(&input, ParseOptions::default()) {
+                let (low, high) = bounds(answer);
+                prop_assert!(low.is_finite() && high.is_finite() && low != 0.0);
+            }
+        }
+
+        #[test]
+        fn never_returns_non_finite_for_huge_numbers(
+            input in "[~≈]?[0-9]{1,400}(\\.[0-9]{1,3})?(e-?[0-9]{1,4})?( ?(x|×) ?10(\\^|\\*\\*)-?[0-9]{1,4})?( ?(k|bn|trillion|E|P|µ))*( ?(-|to) ?[0-9]{1,400}(e[0-9]{1,4})?)?"
+        ) {
+            for mode in [AnswerMode::Value, AnswerMode::Exponent] {
+                let options = ParseOptions { mode, ..ParseOptions::default() };
+                if let Ok(answer) = parse_answer(&input, options) {
+                    let (low, high) = bounds(answer);
+                    prop_assert!(low.is_finite() && high.is_finite(), "{input:?} gave {answer:?}");
+                }
+            }
+        }
+
+        #[test]
+        fn error_spans_are_char_boundaries(input in any::<String>()) {
+            if let Err(err) = parse(&input) {
+                prop_assert!(input.get(err.span.clone()).is_some());
+            }
+        }
+
+        #[test]
+        fn suffix_forms_agree(mantissa in 1u32..1000, (word, letter, scale) in prop_oneof![
+            Just(("thousand", "k", 1e3)),
+            Just(("million", "M", 1e6)),
+            Just(("billion", "B", 1e9)),
+            Just(("trillion", "T", 1e12)),
+        ]) {
+            let expected = f64::from(mantissa) * scale;
+            prop_assert_eq!(parse(&format!("{mantissa} {word}")), Ok(expected));
+            prop_assert_eq!(parse(&format!("{mantissa}{letter}")), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn test_exponent_mode() {
+        let exponent = ParseOptions { mode: AnswerMode::Exponent, ..ParseOptions::default() };
+        assert_eq!(parse_answer("11", exponent).map(value), Ok(1e11));
+        assert_eq!(parse_answer("10^11", exponent).map(value), Ok(1e11));
+        assert_eq!(parse_answer("-3", exponent).map(value), Ok(1e-3));
+        // A negative value needs the "10^" spelled out
+        assert_eq!(parse_answer("-10^11", exponent).map(value), Ok(-1e11));
+        assert_eq!(parse_answer("−10^-3", exponent).map(value), Ok(-1e-3));
+        assert_eq!(parse_answer("10^+3", exponent).map(value), Ok(1e3));
+        assert_eq!(parse_answer("-10", exponent).map(value), Ok(1e-10));
+        assert!((parse_answer("11.6", exponent).map(value).unwrap().log10() - 11.6).abs() < 1e-9);
+        assert_eq!(
+            parse_answer("11B", exponent).map(value).unwrap_err().kind,
+            ParseErrorKind::TrailingGarbage
+        );
+        // Without the mode a bare number is still a plain value
+        assert_eq!(parse("11"), Ok(11.0));
+    }
+
+    #[test]
+    fn test_spelled_numbers() {
+        assert_eq!(parse("four hundred billion"), Ok(400e9));
+        assert_eq!(parse("three and a half million"), Ok(3.5e6));
+        assert_eq!(parse("twelve thousand million"), Ok(12e9));
+        assert_eq!(parse("half a trillion"), Ok(0.5e12));
+        assert_eq!(parse("a million"), Ok(1e6));
+        assert_eq!(parse("Twenty-five thousand."), Ok(25e3));
+        assert_eq!(parse("two hundred and fifty million"), Ok(250e6));
+        assert_eq!(parse("one million two hundred thousand"), Ok(1.2e6));
+        assert_eq!(parse("three quarters of a billion"), Ok(0.75e9));
+        assert_eq!(parse("one and three quarters million"), Ok(1.75e6));
+        assert_eq!(parse("four point five billion dollars"), Ok(4.5e9));
+        assert_eq!(parse("forty B"), Ok(40e9));
+    }
+
+    #[test]
+    fn test_spelled_number_errors() {
+        assert_eq!(parse("lots of money").unwrap_err().kind, ParseErrorKind::InvalidNumber);
+        assert_eq!(parse("four apples").unwrap_err().span, 5..11);
+        assert_eq!(parse("four billion and").unwrap_err().span, 13..16);
+    }
+
+    #[test]
+    fn test_si_and_finance_suffixes() {
+        assert_eq!(parse("4.5bn"), Ok(4.5e9));
+        assert_eq!(parse("4.5 MM"), Ok(4.5e6));
+        assert_eq!(parse("3mn"), Ok(3e6));
+        assert_eq!(parse("2tn"), Ok(2e12));
+        assert_eq!(parse("40 thou"), Ok(40e3));
+        assert_eq!(parse("3 lakh"), Ok(3e5));
+        assert_eq!(parse("4 hundred billion"), Ok(400e9));
+        assert_eq!(parse("4.5G"), Ok(4.5e9));
+        assert_eq!(parse("2 peta"), Ok(2e15));
+        assert_eq!(parse("7P"), Ok(7e15));
+        assert!((parse("5µ").unwrap() / 5e-6 - 1.0).abs() < 1e-12);
+        assert!((parse("5n").unwrap() / 5e-9 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_suffix_style() {
+        let physics = ParseOptions { suffixes: SuffixStyle::Physics, ..ParseOptions::default() };
+        assert_eq!(parse("5m"), Ok(5e6));
+        assert_eq!(parse_answer("5m", physics).map(value), Ok(5e-3));
+        assert_eq!(parse_answer("5M", physics).map(value), Ok(5e6));
+        assert_eq!(parse_answer("5E", physics).map(value), Ok(5e18));
+        assert_eq!(parse_answer("5bn", physics).map(value), Ok(5e9));
+        assert_eq!(parse_answer("5 milli", physics).map(value), Ok(5e-3));
+        assert_eq!(parse_answer("5b", physics).map(value).unwrap_err().kind, ParseErrorKind::UnknownSuffix);
+        assert_eq!(parse_answer("5e", physics).map(value).unwrap_err().kind, ParseErrorKind::MalformedExponent);
+    }
+
+    #[test]
+    fn test_scientific_notation_forms() {
+        assert_eq!(parse("4×10¹¹"), Ok(4e11));
+        assert_eq!(parse("4 × 10⁺¹¹"), Ok(4e11));
+        assert!((parse("10⁻³").unwrap() - 1e-3).abs() < 1e-15);
+        assert_eq!(parse("4*10**11"), Ok(4e11));
+        assert_eq!(parse("10**11"), Ok(1e11));
+        assert_eq!(parse("4.00E+11"), Ok(4e11));
+        assert_eq!(parse("4 E+11"), Ok(4e11));
+        assert_eq!(parse("4·10^11"), Ok(4e11));
+        assert_eq!(parse("4 ⋅ 10^11"), Ok(4e11));
+        assert_eq!(parse("10^11"), Ok(1e11));
+        assert_eq!(parse("10 ^ 11 dollars"), Ok(1e11));
+        assert!((parse("10^-3").unwrap() - 1e-3).abs() < 1e-15);
+        assert!((parse("4e-3").unwrap() - 4e-3).abs() < 1e-15);
+        assert!((parse("4 x 10^-3").unwrap() - 4e-3).abs() < 1e-15);
+        assert!((parse("4 E-3").unwrap() - 4e-3).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_malformed_powers() {
+        assert_eq!(parse("4 x 10").unwrap_err().kind, ParseErrorKind::MalformedExponent);
+        assert_eq!(parse("4 x 10⁻").unwrap_err().kind, ParseErrorKind::MalformedExponent);
+        assert_eq!(parse("4 E+").unwrap_err().kind, ParseErrorKind::MalformedExponent);
+        assert_eq!(parse("10^").unwrap_err().kind, ParseErrorKind::MalformedExponent);
+    }
+
+    #[test]
+    fn test_grouping_separators() {
+        assert_eq!(parse("1'000'000"), Ok(1e6));
+        assert_eq!(parse("1’000’000"), Ok(1e6));
+        assert_eq!(parse("1\u{2009}000\u{2009}000"), Ok(1e6));
+        assert_eq!(parse("1\u{202F}000.5"), Ok(1000.5));
+        // A comma that can't be grouping is an error, not silently dropped
+        assert_eq!(parse("4,5 million").unwrap_err().kind, ParseErrorKind::TrailingGarbage);
+    }
+
+    #[test]
+    fn test_comma_decimal() {
+        let comma = ParseOptions { decimal: DecimalSeparator::Comma, ..ParseOptions::default() };
+        assert_eq!(parse_answer("4,5 Mio", comma).map(value), Ok(4.5e6));
+        assert_eq!(parse_answer("4,5 Mrd", comma).map(value), Ok(4.5e9));
+        assert_eq!(parse_answer("1.000.000", comma).map(value), Ok(1e6));
+        assert_eq!(parse_answer("1.000.000,25", comma).map(value), Ok(1_000_000.25));
+        assert_eq!(parse_answer("1 000 000", comma).map(value), Ok(1e6));
+        assert_eq!(parse_answer("4,5e11", comma).map(value), Ok(4.5e11));
+        assert_eq!(parse_answer("1,000", comma).map(value), Ok(1.0));
+        assert_eq!(parse_answer("4.5", comma).map(value).unwrap_err().kind, ParseErrorKind::TrailingGarbage);
+    }
+
+    #[test]
+    fn test_decimal_separator_for_language() {
+        assert_eq!(DecimalSeparator::for_language("en-US"), DecimalSeparator::Point);
+        assert_eq!(DecimalSeparator::for_language("de-DE"), DecimalSeparator::Comma);
+        assert_eq!(DecimalSeparator::for_language("fr"), DecimalSeparator::Comma);
+        assert_eq!(DecimalSeparator::for_language("de-CH"), DecimalSeparator::Point);
+        assert_eq!(DecimalSeparator::for_language("it-CH"), DecimalSeparator::Point);
+        // The Swiss exception is German and Italian only
+        assert_eq!(DecimalSeparator::for_language("fr-CH"), DecimalSeparator::Comma);
+        // Most of Latin America and the US write Spanish with a decimal point
+        assert_eq!(DecimalSeparator::for_language("es-MX"), DecimalSeparator::Point);
+        assert_eq!(DecimalSeparator::for_language("es_us"), DecimalSeparator::Point);
+        assert_eq!(DecimalSeparator::for_language("es-419"), DecimalSeparator::Point);
+        assert_eq!(DecimalSeparator::for_language("es-AR"), DecimalSeparator::Comma);
+        assert_eq!(DecimalSeparator::for_language("es-ES"), DecimalSeparator::Comma);
+        assert_eq!(DecimalSeparator::for_language("de-Latn-CH"), DecimalSeparator::Point);
+        assert_eq!(DecimalSeparator::for_language(""), DecimalSeparator::Point);
+    }
+
+    #[test]
+    fn test_approximate_answers() {
+        assert_eq!(parse("~400B"), Ok(400e9));
+        assert_eq!(parse("≈4e11"), Ok(4e11));
+        assert_eq!(parse("about 400 billion"), Ok(400e9));
+        assert_eq!(parse("approx. 4 x 10^11"), Ok(4e11));
+        assert_eq!(parse("roughly half a trillion"), Ok(0.5e12));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let range = |input| parse_answer(input, ParseOptions::default()).map(bounds);
+        assert_eq!(range("300–500B"), Ok((300e9, 500e9)));
+        assert_eq!(range("300-500B"), Ok((300e9, 500e9)));
+        assert_eq!(range("3e11 to 5e11"), Ok((3e11, 5e11)));
+        assert_eq!(range("300M to 5B"), Ok((300e6, 5e9)));
+        assert_eq!(range("three to five million"), Ok((3e6, 5e6)));
+        assert_eq!(range("~500 — ~300 billion"), Ok((300e9, 500e9)));
+        assert!(matches!(parse_answer("4B-4B", ParseOptions::default()), Ok(Answer::Point(_))));
+        assert_eq!(range("300 to").unwrap_err().kind, ParseErrorKind::InvalidNumber);
+        assert!((parse("1e9 to 1e11").unwrap() - 1e10).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(parse("1e999").unwrap_err().kind, ParseErrorKind::OutOfRange);
+        assert_eq!(parse(" 0 x 10^999").unwrap_err().span, 1..11);
+        let exponent = ParseOptions { mode: AnswerMode::Exponent, ..ParseOptions::default() };
+        assert_eq!(parse_answer("400", exponent).unwrap_err().kind, ParseErrorKind::OutOfRange);
+        assert!(parse("1e200 to 1e300").unwrap().is_finite());
+    }
+
+    #[test]
+    fn test_signs_and_zero() {
+        assert_eq!(parse("-5"), Ok(-5.0));
+        assert_eq!(parse("−4.5 billion"), Ok(-4.5e9));
+        assert_eq!(parse("0"), Err(ParseError { kind: ParseErrorKind::Zero, span: 0..1 }));
+        assert_eq!(parse("-5 to 5").unwrap_err().kind, ParseErrorKind::RangeCrossesZero);
+        assert_eq!(
+            parse_answer("-500 to -300B", ParseOptions::default()).map(bounds),
+            Ok((-500e9, -300e9))
+        );
+        assert_eq!(parse("nan").unwrap_err().kind, ParseErrorKind::InvalidNumber);
+        assert_eq!(parse("inf").unwrap_err().kind, ParseErrorKind::InvalidNumber);
+    }
+
+    #[test]
+    fn test_sign_around_currency() {
+        assert_eq!(parse("-$5"), Ok(-5.0));
+        assert_eq!(parse("$-5"), Ok(-5.0));
+        assert_eq!(parse("−$4.5B"), Ok(-4.5e9));
+        assert_eq!(parse("+$5"), Ok(5.0));
+        assert_eq!(parse("-$-5").unwrap_err().kind, ParseErrorKind::InvalidNumber);
     }
 }
@@ -0,0 +1,187 @@
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::CharIndices;
+
+use super::{DecimalSeparator, ParseError, ParseErrorKind};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenKind {
+    /// A numeric literal; `integer` is false if it had a decimal point or exponent
+    Number { value: f64, integer: bool },
+    /// A run of letters, lowercased
+    Word(String),
+    Plus,
+    Minus,
+    /// An en or em dash, which only separates a range
+    Dash,
+    /// "~" or "≈"
+    Approx,
+    Times,
+    /// `^` or `**`
+    Caret,
+    /// A superscript exponent such as "¹¹" or "⁻³"
+    Superscript(f64),
+    Period,
+    Currency,
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Range<usize>,
+}
+
+/// Split `input` into tokens whose spans index into `input` itself.
+pub fn tokenize(input: &str, decimal: DecimalSeparator) -> Result<Vec<Token>, ParseError> {
+    let mut lexer = Lexer { input, chars: input.char_indices().peekable(), decimal };
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token()? {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Lexer<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    decimal: DecimalSeparator,
+}
+
+impl Lexer<'_> {
+    fn next_token(&mut self) -> Result<Option<Token>, ParseError> {
+        while self.chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {}
+
+        let Some(&(start, c)) = self.chars.peek() else {
+            return Ok(None);
+        };
+
+        if c.is_ascii_digit() || (c == self.decimal.decimal_char() && self.digit_at(start + 1)) {
+            return self.number(start).map(Some);
+        }
+
+        if c.is_alphabetic() {
+            let end = self.take_while(char::is_alphabetic);
+            let word = self.input[start..end].to_lowercase();
+            // A lone "x" between numbers is a multiplication sign, not a word
+            let kind = if word == "x" { TokenKind::Times } else { TokenKind::Word(word) };
+            return Ok(Some(Token { kind, span: start..end }));
+        }
+
+        if superscript_digit(c).is_some() || matches!(c, '⁻' | '⁺') {
+            return Ok(Some(self.superscript(start)));
+        }
+
+        self.chars.next();
+        let kind = match c {
+            '+' => TokenKind::Plus,
+            '-' | '−' => TokenKind::Minus,
+            '–' | '—' => TokenKind::Dash,
+            '~' | '≈' => TokenKind::Approx,
+            '*' if self.chars.next_if(|&(_, c)| c == '*').is_some() => TokenKind::Caret,
+            '×' | '*' | '·' | '⋅' => TokenKind::Times,
+            '^' => TokenKind::Caret,
+            '.' => TokenKind::Period,
+            '$' | '€' | '£' | '¥' => TokenKind::Currency,
+            _ => TokenKind::Other,
+        };
+        Ok(Some(Token { kind, span: start..start + c.len_utf8() }))
+    }
+
+    /// Digits with optional thousands grouping, a decimal part, and an
+    /// exponent attached directly to the digits ("4.5e11").
+    fn number(&mut self, start: usize) -> Result<Token, ParseError> {
+        let mut digits = String::new();
+        let mut integer = true;
+
+        loop {
+            while let Some((_, d)) = self.chars.next_if(|&(_, c)| c.is_ascii_digit()) {
+                digits.push(d);
+            }
+            match self.chars.peek().copied() {
+                Some((i, c)) if self.decimal.is_group_char(c) && self.is_digit_group(i + c.len_utf8()) => {
+                    self.chars.next();
+                }
+                _ => break,
+            }
+        }
+
+        let decimal = self.decimal.decimal_char();
+        if self.chars.next_if(|&(_, c)| c == decimal).is_some() {
+            integer = false;
+            digits.push('.');
+            while let Some((_, d)) = self.chars.next_if(|&(_, c)| c.is_ascii_digit()) {
+                digits.push(d);
+            }
+        }
+
+        // A bare trailing 'e' is left as a word, since "5E" can mean exa
+        if let Some((e_pos, 'e' | 'E')) = self.chars.peek().copied() {
+            if self.input[e_pos + 1..].starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-')) {
+                self.chars.next();
+                integer = false;
+                digits.push('e');
+                if let Some((_, sign)) = self.chars.next_if(|&(_, c)| matches!(c, '+' | '-')) {
+                    digits.push(sign);
+                }
+                let exp_start = digits.len();
+                while let Some((_, d)) = self.chars.next_if(|&(_, c)| c.is_ascii_digit()) {
+                    digits.push(d);
+                }
+                if digits.len() == exp_start {
+                    let end = self.take_while(|c| !c.is_whitespace());
+                    return Err(ParseError::new(ParseErrorKind::MalformedExponent, e_pos..end));
+                }
+            }
+        }
+
+        let end = self.position();
+        let value = digits
+            .parse()
+            .map_err(|_| ParseError::new(ParseErrorKind::InvalidNumber, start..end))?;
+        Ok(Token { kind: TokenKind::Number { value, integer }, span: start..end })
+    }
+
+    fn superscript(&mut self, start: usize) -> Token {
+        let negative = self.chars.next_if(|&(_, c)| matches!(c, '⁻' | '⁺')).is_some_and(|(_, c)| c == '⁻');
+        let mut exponent = 0.0;
+        let mut any = false;
+        while let Some(digit) = self.chars.peek().and_then(|&(_, c)| superscript_digit(c)) {
+            self.chars.next();
+            exponent = exponent * 10.0 + f64::from(digit);
+            any = true;
+        }
+        let end = self.position();
+        let kind = match (any, negative) {
+            (false, _) => TokenKind::Other,
+            (true, true) => TokenKind::Superscript(-exponent),
+            (true, false) => TokenKind::Superscript(exponent),
+        };
+        Token { kind, span: start..end }
+    }
+
+    /// A thousands group: exactly three digits not followed by another digit.
+    fn is_digit_group(&self, at: usize) -> bool {
+        let rest = &self.input.as_bytes()[at..];
+        rest.len() >= 3
+            && rest[..3].iter().all(u8::is_ascii_digit)
+            && !rest.get(3).is_some_and(u8::is_ascii_digit)
+    }
+
+    fn digit_at(&self, at: usize) -> bool {
+        self.input[at..].starts_with(|c: char| c.is_ascii_digit())
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> usize {
+        while self.chars.next_if(|&(_, c)| pred(c)).is_some() {}
+        self.position()
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.input.len(), |&(i, _)| i)
+    }
+}
+
+fn superscript_digit(c: char) -> Option<u8> {
+    "⁰¹²³⁴⁵⁶⁷⁸⁹".chars().position(|s| s == c).and_then(|d| u8::try_from(d).ok())
+}
@@ -0,0 +1,160 @@
+use super::lexer::{Token, TokenKind};
+use super::WORD_SUFFIXES;
+
+const UNITS: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen",
+];
+
+const TENS: [(&str, f64); 8] = [
+    ("twenty", 20.0),
+    ("thirty", 30.0),
+    ("forty", 40.0),
+    ("fifty", 50.0),
+    ("sixty", 60.0),
+    ("seventy", 70.0),
+    ("eighty", 80.0),
+    ("ninety", 90.0),
+];
+
+const FRACTIONS: [(&str, f64); 7] = [
+    ("half", 0.5),
+    ("halves", 0.5),
+    ("quarter", 0.25),
+    ("quarters", 0.25),
+    ("third", 1.0 / 3.0),
+    ("thirds", 1.0 / 3.0),
+    ("fifth", 0.2),
+];
+
+pub struct Spelled {
+    pub value: f64,
+    /// The largest scale word used, as in "five million"
+    pub scale: Option<f64>,
+    /// How many tokens the number took up
+    pub len: usize,
+}
+
+/// Read a spelled-out number such as "three and a half million" or
+/// "twelve thousand million" from the start of `tokens`.
+///
+/// Returns `None` if the first token does not start a number.
+pub fn spelled_number(tokens: &[Token]) -> Option<Spelled> {
+    let mut reader = Reader { tokens, pos: 0 };
+    let mut total = 0.0;
+    // Whole part set aside by "and", as in "three and a half"
+    let mut whole = 0.0;
+    let mut current = 0.0;
+    let mut largest_scale = 0.0;
+    let mut seen = false;
+
+    while let Some(word) = reader.word(0) {
+        let next = reader.word(1);
+        if let Some(n) = unit_value(word) {
+            current += n;
+        } else if let Some(n) = tens_value(word) {
+            current += n;
+            // "twenty-one"
+            if reader.kind(1) == Some(&TokenKind::Minus) && reader.word(2).and_then(unit_value).is_some() {
+                reader.pos += 1;
+            }
+        } else if word == "hundred" {
+            current = if current == 0.0 { 100.0 } else { current * 100.0 };
+        } else if let Some(scale) = scale_value(word) {
+            let mut count = whole + current;
+            if count == 0.0 && total == 0.0 {
+                count = 1.0;
+            }
+            if scale > largest_scale {
+                // "twelve thousand million" scales everything before it
+                total = (total + count) * scale;
+                largest_scale = scale;
+            } else {
+                total += count * scale;
+            }
+            whole = 0.0;
+            current = 0.0;
+        } else if let Some(fraction) = fraction_value(word) {
+            // "half" on its own, or "three quarters"
+            current = if current == 0.0 { fraction } else { current * fraction };
+            // "half of a million"
+            if next == Some("of") {
+                reader.pos += 1;
+                if matches!(reader.word(1), Some("a" | "an")) {
+                    reader.pos += 1;
+                }
+            }
+        } else if word == "and" && seen && next.is_some_and(starts_number) {
+            whole += current;
+            current = 0.0;
+        } else if matches!(word, "a" | "an") && next.is_some_and(follows_article) {
+            // "a million" counts one; in "half a trillion" the article adds nothing
+            if !seen && next.and_then(fraction_value).is_none() {
+                current = 1.0;
+            }
+        } else if word == "point" && seen {
+            let mut place = 0.1;
+            while let Some(digit) = reader.word(1).and_then(unit_value).filter(|&d| d < 10.0) {
+                current += digit * place;
+                place /= 10.0;
+                reader.pos += 1;
+            }
+        } else {
+            break;
+        }
+        seen = true;
+        reader.pos += 1;
+    }
+
+    seen.then_some(Spelled {
+        value: total + whole + current,
+        scale: (largest_scale > 0.0).then_some(largest_scale),
+        len: reader.pos,
+    })
+}
+
+fn starts_number(word: &str) -> bool {
+    matches!(word, "a" | "an")
+        || unit_value(word).is_some()
+        || tens_value(word).is_some()
+        || fraction_value(word).is_some()
+}
+
+fn follows_article(word: &str) -> bool {
+    word == "hundred" || scale_value(word).is_some() || fraction_value(word).is_some()
+}
+
+fn unit_value(word: &str) -> Option<f64> {
+    UNITS.iter().position(|&u| u == word).map(|n| n as f64)
+}
+
+fn tens_value(word: &str) -> Option<f64> {
+    TENS.iter().find(|&&(w, _)| w == word).map(|&(_, n)| n)
+}
+
+fn fraction_value(word: &str) -> Option<f64> {
+    FRACTIONS.iter().find(|&&(w, _)| w == word).map(|&(_, n)| n)
+}
+
+fn scale_value(word: &str) -> Option<f64> {
+    WORD_SUFFIXES.iter().find(|&&(w, _)| w == word).map(|&(_, n)| n)
+}
+
+struct Reader<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn kind(&self, ahead: usize) -> Option<&'a TokenKind> {
+        self.tokens.get(self.pos + ahead).map(|t| &t.kind)
+    }
+
+    fn word(&self, ahead: usize) -> Option<&'a str> {
+        match self.kind(ahead) {
+            Some(TokenKind::Word(word)) => Some(word),
+            _ => None,
+        }
+    }
+}
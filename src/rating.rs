@@ -0,0 +1,197 @@
+use std::f64::consts::LN_10;
+
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::{Challenge, Operation};
+use crate::scoring::{Attempt, RuleKind};
+
+/// A Glicko-1 rating: `value` on the familiar Elo scale and `deviation`, the
+/// uncertainty around it, which shrinks with play and grows with absence.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub value: f64,
+    pub deviation: f64,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self { value: 1500.0, deviation: Self::MAX_DEVIATION }
+    }
+}
+
+impl Rating {
+    const MAX_DEVIATION: f64 = 350.0;
+    /// Keeps a long-standing rating responsive to new answers
+    const MIN_DEVIATION: f64 = 30.0;
+    /// Deviation regained per day away, in quadrature: about 100 days from
+    /// settled back to unknown
+    const DAILY_DRIFT: f64 = 35.0;
+    const Q: f64 = LN_10 / 400.0;
+
+    /// One "game" against a problem rated `difficulty`, with `outcome` from 0
+    /// (lost) to 1 (won). Problems count as exactly rated opponents.
+    pub fn update(self, difficulty: f64, outcome: f64) -> Self {
+        let expected = 1.0 / (1.0 + 10_f64.powf((difficulty - self.value) / 400.0));
+        let d_squared = 1.0 / (Self::Q * Self::Q * expected * (1.0 - expected));
+        let precision = 1.0 / (self.deviation * self.deviation) + 1.0 / d_squared;
+        Self {
+            value: self.value + Self::Q / precision * (outcome - expected),
+            deviation: (1.0 / precision).sqrt().max(Self::MIN_DEVIATION),
+        }
+    }
+
+    /// Widen the uncertainty after `days` without play.
+    pub fn decay(self, days: i64) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let drift = Self::DAILY_DRIFT * Self::DAILY_DRIFT * days.max(0) as f64;
+        let deviation = (self.deviation * self.deviation + drift).sqrt().min(Self::MAX_DEVIATION);
+        Self { deviation, ..self }
+    }
+}
+
+/// A rough opponent rating for a problem: division, negative operands and a
+/// mantissa step that crosses a power of ten each make it harder.
+pub fn difficulty(challenge: &Challenge) -> f64 {
+    let mut rating = 1400.0;
+    if challenge.operation() == Operation::Divide {
+        rating += 200.0;
+    }
+    if challenge.num1 < 0.0 || challenge.num2 < 0.0 {
+        rating += 100.0;
+    }
+    if challenge.carries() {
+        rating += 100.0;
+    }
+    rating
+}
+
+/// How well an answer "won", from 0 to 1. Uses the continuous curve whatever
+/// rule the player chose, so ratings stay comparable across rules.
+pub fn outcome(attempt: &Attempt) -> f64 {
+    f64::from(RuleKind::Continuous.rule().points(attempt)) / 100.0
+}
+
+/// Everything persisted between sessions: the overall rating, a sub-rating
+/// per operation, and where the overall rating stood when today began.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RatingState {
+    pub overall: Rating,
+    pub multiply: Rating,
+    pub divide: Rating,
+    /// Local day number the state was last rolled over to by `begin_day`.
+    /// It is only saved after an answer, so a stored state's day is the last
+    /// day played.
+    pub day: i64,
+    /// Overall rating when `day` began, i.e. at the end of the previous day played
+    pub start_of_day: f64,
+}
+
+impl RatingState {
+    /// Roll over to `today`, widening deviations for the days missed.
+    pub fn begin_day(self, today: i64) -> Self {
+        if today == self.day {
+            return self;
+        }
+        let days = if self.day == 0 { 0 } else { today - self.day };
+        Self {
+            overall: self.overall.decay(days),
+            multiply: self.multiply.decay(days),
+            divide: self.divide.decay(days),
+            day: today,
+            start_of_day: self.overall.value,
+        }
+    }
+
+    pub fn record(self, challenge: &Challenge, attempt: &Attempt) -> Self {
+        let (difficulty, outcome) = (difficulty(challenge), outcome(attempt));
+        let mut next = Self { overall: self.overall.update(difficulty, outcome), ..self };
+        match challenge.operation() {
+            Operation::Multiply => next.multiply = self.multiply.update(difficulty, outcome),
+            Operation::Divide => next.divide = self.divide.update(difficulty, outcome),
+        }
+        next
+    }
+
+    /// Change since the end of the last day played
+    pub fn change_today(&self) -> f64 {
+        self.overall.value - self.start_of_day
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::Estimate;
+
+    fn challenge(num1: f64, num2: f64, is_division: bool) -> Challenge {
+        Challenge { num1, num2, is_division }
+    }
+
+    fn attempt(challenge: &Challenge, answer: f64) -> Attempt {
+        let answer = Estimate::new(answer).unwrap();
+        Attempt {
+            low: answer,
+            high: answer,
+            correct: Estimate::new(challenge.answer()).unwrap(),
+            operation: challenge.operation(),
+            seconds: 5.0,
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let start = Rating::default();
+        let won = start.update(1500.0, 1.0);
+        let lost = start.update(1500.0, 0.0);
+        assert!(won.value > start.value && lost.value < start.value);
+        assert!(won.deviation < start.deviation);
+        // Beating a harder problem is worth more
+        assert!(start.update(1800.0, 1.0).value > won.value);
+        // Deviation bottoms out
+        let settled = (0..1000).fold(start, |r, _| r.update(1500.0, 0.5));
+        assert!((settled.deviation - Rating::MIN_DEVIATION).abs() < 1e-9);
+        assert!((settled.value - 1500.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_decay() {
+        let settled = Rating { value: 1600.0, deviation: 50.0 };
+        assert_eq!(settled.decay(0), settled);
+        assert!(settled.decay(10).deviation > 50.0);
+        assert!((settled.decay(10_000).deviation - Rating::MAX_DEVIATION).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_difficulty() {
+        let easy = challenge(2e6, 3e3, false);
+        assert!((difficulty(&easy) - 1400.0).abs() < 1e-9);
+        assert!(difficulty(&challenge(4e6, 3e3, false)) > difficulty(&easy));
+        assert!(difficulty(&challenge(2e6, 3e3, true)) > difficulty(&easy));
+        assert!(difficulty(&challenge(-2e6, 3e3, false)) > difficulty(&easy));
+    }
+
+    #[test]
+    fn test_rating_state() {
+        let multiply = challenge(2e6, 3e3, false);
+        let state = RatingState::default().begin_day(100);
+        assert_eq!(state.day, 100);
+        let state = state.record(&multiply, &attempt(&multiply, 6e9));
+        assert!(state.change_today() > 0.0);
+        assert_eq!(state.divide, Rating::default());
+        assert!(state.multiply.value > 1500.0);
+
+        let next_day = state.begin_day(103);
+        assert!((next_day.start_of_day - state.overall.value).abs() < 1e-9);
+        assert!(next_day.change_today().abs() < 1e-9);
+        assert!(next_day.overall.deviation > state.overall.deviation);
+        assert_eq!(next_day.begin_day(103), next_day);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let multiply = challenge(2e6, 3e3, false);
+        let state = RatingState::default().begin_day(5).record(&multiply, &attempt(&multiply, 6e8));
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<RatingState>(&json).unwrap(), state);
+    }
+}
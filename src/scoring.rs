@@ -1,17 +1,127 @@
-/// Calculate the order of magnitude distance between two numbers
-pub fn oom_distance(user_answer: f64, correct_answer: f64) -> f64 {
-    if user_answer <= 0.0 || correct_answer <= 0.0 {
-        return f64::MAX;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::Operation;
+
+/// A finite, nonzero value that can be scored. [`Estimate::new`] is the
+/// only way to build one, so NaN, infinity and zero never reach the scorer.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Estimate(f64);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EstimateError {
+    NotFinite,
+    Zero,
+}
+
+impl fmt::Display for EstimateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NotFinite => "estimate is not a finite number",
+            Self::Zero => "estimate can't be zero",
+        })
     }
+}
+
+impl TryFrom<f64> for Estimate {
+    type Error = EstimateError;
 
-    (user_answer.log10() - correct_answer.log10()).abs()
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+impl From<Estimate> for f64 {
+    fn from(estimate: Estimate) -> Self {
+        estimate.0
+    }
+}
+
+impl Estimate {
+    pub fn new(value: f64) -> Result<Self, EstimateError> {
+        if !value.is_finite() {
+            Err(EstimateError::NotFinite)
+        } else if value == 0.0 {
+            Err(EstimateError::Zero)
+        } else {
+            Ok(Self(value))
+        }
+    }
+
+    /// Midpoint on a log scale, keeping the sign of `self`; only meaningful
+    /// for two estimates of the same sign.
+    pub fn geometric_mean(self, other: Self) -> Self {
+        if self == other {
+            return self;
+        }
+        Self(self.0.signum() * self.0.abs().sqrt() * other.0.abs().sqrt())
+    }
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0.0
+    }
+}
+
+/// Signed error in orders of magnitude, ignoring sign: positive when the
+/// answer is too large, negative when it is too small
+pub fn log_error(user_answer: Estimate, correct_answer: Estimate) -> f64 {
+    user_answer.0.abs().log10() - correct_answer.0.abs().log10()
+}
+
+/// Calculate the order of magnitude distance between two numbers, ignoring sign
+pub fn oom_distance(user_answer: Estimate, correct_answer: Estimate) -> f64 {
+    log_error(user_answer, correct_answer).abs()
+}
+
+/// [`log_error`] split into whole orders of magnitude ("counted the zeros
+/// wrong") and the mantissa's share ("multiplied the mantissas wrong"). The
+/// mantissa part stays within ±0.5 OOM, so 9.8e5 against 1.02e6 has the
+/// exponent right rather than one too low.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Components {
+    pub exponent_error: i32,
+    /// In orders of magnitude
+    pub mantissa_error: f64,
+}
+
+impl Components {
+    pub fn new(user_answer: Estimate, correct_answer: Estimate) -> Self {
+        let error = log_error(user_answer, correct_answer);
+        let exponent = error.round();
+        #[allow(clippy::cast_possible_truncation)]
+        let exponent_error = exponent as i32;
+        Self { exponent_error, mantissa_error: error - exponent }
+    }
+
+    pub fn exponent_correct(self) -> bool {
+        self.exponent_error == 0
+    }
+
+    /// How far off the mantissa was, as a percentage: +12.0 means 12% too high
+    pub fn mantissa_percent(self) -> f64 {
+        (10_f64.powf(self.mantissa_error) - 1.0) * 100.0
+    }
+}
+
+/// Mean signed log error, or `None` for no answers. Negative means the
+/// player tends to underestimate.
+pub fn mean_bias(errors: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = errors.into_iter().fold((0.0, 0u32), |(sum, count), e| (sum + e, count + 1));
+    (count > 0).then(|| sum / f64::from(count))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ScoreResult {
     Exact,      // Within 0.1 OOM
     Close,      // Within 0.5 OOM
     Partial,    // Within 1.0 OOM
+    WrongSign,  // Within 0.5 OOM, but with the wrong sign
     Wrong,      // More than 1.0 OOM off
 }
 
@@ -20,7 +130,7 @@ impl ScoreResult {
         match self {
             Self::Exact => 100,
             Self::Close => 75,
-            Self::Partial => 25,
+            Self::Partial | Self::WrongSign => 25,
             Self::Wrong => 0,
         }
     }
@@ -30,42 +140,515 @@ impl ScoreResult {
             Self::Exact => "Exact!",
             Self::Close => "Close!",
             Self::Partial => "Partial",
+            Self::WrongSign => "Wrong sign",
             Self::Wrong => "Off",
         }
     }
 }
 
-pub fn evaluate(user_answer: f64, correct_answer: f64) -> ScoreResult {
+/// Each band and its outer edge in OOM, best first; anything further is Wrong
+pub const BANDS: [(ScoreResult, f64); 3] =
+    [(ScoreResult::Exact, 0.1), (ScoreResult::Close, 0.5), (ScoreResult::Partial, 1.0)];
+
+pub fn evaluate(user_answer: Estimate, correct_answer: Estimate) -> ScoreResult {
     let distance = oom_distance(user_answer, correct_answer);
 
-    if distance <= 0.1 {
-        ScoreResult::Exact
-    } else if distance <= 0.5 {
-        ScoreResult::Close
-    } else if distance <= 1.0 {
-        ScoreResult::Partial
-    } else {
-        ScoreResult::Wrong
+    if user_answer.is_negative() != correct_answer.is_negative() {
+        return if distance <= 0.5 { ScoreResult::WrongSign } else { ScoreResult::Wrong };
+    }
+
+    BANDS.iter().find(|&&(_, edge)| distance <= edge).map_or(ScoreResult::Wrong, |&(result, _)| result)
+}
+
+/// Everything a [`ScoringRule`] sees of one answer. A point answer is an
+/// interval with `low == high`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attempt {
+    pub low: Estimate,
+    pub high: Estimate,
+    pub correct: Estimate,
+    pub operation: Operation,
+    /// From the problem appearing to the answer being submitted
+    pub seconds: f64,
+}
+
+impl Attempt {
+    /// The single value to score: the point itself, or the geometric
+    /// midpoint of a range since errors are measured in orders of magnitude.
+    pub fn estimate(&self) -> Estimate {
+        self.low.geometric_mean(self.high)
     }
+
+    pub fn is_interval(&self) -> bool {
+        self.low != self.high
+    }
+
+    /// Whether the truth lies within the bounds, sign included
+    pub fn contains(&self) -> bool {
+        let (low, high, correct) = (self.low.get(), self.high.get(), self.correct.get());
+        low.min(high) <= correct && correct <= low.max(high)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub result: ScoreResult,
+    pub points: u32,
+    /// The rule that produced `points`, so old results stay interpretable
+    pub rule: RuleKind,
+    /// See [`log_error`]
+    pub log_error: f64,
+    pub components: Components,
+}
+
+pub trait ScoringRule {
+    fn kind(&self) -> RuleKind;
+    fn points(&self, attempt: &Attempt) -> u32;
+
+    /// The band label is shared by every rule; only the points differ.
+    fn score(&self, attempt: &Attempt) -> Score {
+        Score {
+            result: evaluate(attempt.estimate(), attempt.correct),
+            points: self.points(attempt),
+            rule: self.kind(),
+            log_error: log_error(attempt.estimate(), attempt.correct),
+            components: Components::new(attempt.estimate(), attempt.correct),
+        }
+    }
+}
+
+/// The built-in rules, as recorded with each result.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleKind {
+    #[default]
+    Banded,
+    Continuous,
+    Strict,
+    IntervalLog,
+    PerOperation,
+    IntervalScore,
+    SpeedWeighted,
+}
+
+impl RuleKind {
+    pub const ALL: [Self; 7] = [
+        Self::Banded,
+        Self::Continuous,
+        Self::Strict,
+        Self::IntervalLog,
+        Self::PerOperation,
+        Self::IntervalScore,
+        Self::SpeedWeighted,
+    ];
+
+    pub fn rule(self) -> Box<dyn ScoringRule> {
+        match self {
+            Self::Banded => Box::new(Banded),
+            Self::Continuous => Box::new(Continuous),
+            Self::Strict => Box::new(Strict),
+            Self::IntervalLog => Box::new(IntervalLog),
+            Self::PerOperation => Box::new(PerOperation { multiply: Box::new(Strict), divide: Box::new(Banded) }),
+            Self::IntervalScore => Box::new(IntervalScore { coverage: INTERVAL_COVERAGE }),
+            Self::SpeedWeighted => Box::new(SpeedWeighted { base: Box::new(Banded) }),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Banded => "Bands",
+            Self::Continuous => "Smooth",
+            Self::Strict => "Strict",
+            Self::IntervalLog => "Interval",
+            Self::PerOperation => "By type",
+            Self::IntervalScore => "80% interval",
+            Self::SpeedWeighted => "Speed",
+        }
+    }
+}
+
+/// 100/75/25/0 by [`ScoreResult`] band.
+pub struct Banded;
+
+impl ScoringRule for Banded {
+    fn kind(&self) -> RuleKind {
+        RuleKind::Banded
+    }
+
+    fn points(&self, attempt: &Attempt) -> u32 {
+        evaluate(attempt.estimate(), attempt.correct).points()
+    }
+}
+
+/// Points decaying smoothly with log error, see [`continuous_points`].
+pub struct Continuous;
+
+impl ScoringRule for Continuous {
+    fn kind(&self) -> RuleKind {
+        RuleKind::Continuous
+    }
+
+    fn points(&self, attempt: &Attempt) -> u32 {
+        let (user, correct) = (attempt.estimate(), attempt.correct);
+        let points = continuous_points(oom_distance(user, correct));
+        // A wrong sign keeps a third, as the bands do going from Close to WrongSign
+        if user.is_negative() == correct.is_negative() { points } else { points / 3 }
+    }
+}
+
+/// All or nothing: 100 for the Exact band, 0 otherwise.
+pub struct Strict;
+
+impl ScoringRule for Strict {
+    fn kind(&self) -> RuleKind {
+        RuleKind::Strict
+    }
+
+    fn points(&self, attempt: &Attempt) -> u32 {
+        match evaluate(attempt.estimate(), attempt.correct) {
+            ScoreResult::Exact => 100,
+            _ => 0,
+        }
+    }
+}
+
+/// Log score of a log-uniform belief over the interval: 100 for a hit at most
+/// 0.1 OOM wide, 50 at 1 OOM wide, nothing at 10 OOM wide or for a miss. A
+/// point answer counts as 0.1 OOM wide.
+pub struct IntervalLog;
+
+impl IntervalLog {
+    const MIN_WIDTH: f64 = 0.1;
+}
+
+impl ScoringRule for IntervalLog {
+    fn kind(&self) -> RuleKind {
+        RuleKind::IntervalLog
+    }
+
+    fn points(&self, attempt: &Attempt) -> u32 {
+        let (low, high, correct) = (attempt.low, attempt.high, attempt.correct);
+        if low.is_negative() != correct.is_negative() {
+            return 0;
+        }
+        let width = oom_distance(low, high).max(Self::MIN_WIDTH);
+        // Distance from the truth to the nearer edge of the widened interval
+        let center = attempt.estimate();
+        if oom_distance(center, correct) > width / 2.0 {
+            return 0;
+        }
+        let sharpness = 1.0 - (width / Self::MIN_WIDTH).log10() / 2.0;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let points = (100.0 * sharpness.max(0.0)).round() as u32;
+        points
+    }
+}
+
+/// A different rule per operation. The preset holds multiplication to the
+/// Exact band, since it is just multiplying mantissas and adding exponents.
+pub struct PerOperation {
+    pub multiply: Box<dyn ScoringRule>,
+    pub divide: Box<dyn ScoringRule>,
+}
+
+impl ScoringRule for PerOperation {
+    fn kind(&self) -> RuleKind {
+        RuleKind::PerOperation
+    }
+
+    fn points(&self, attempt: &Attempt) -> u32 {
+        match attempt.operation {
+            Operation::Multiply => self.multiply.points(attempt),
+            Operation::Divide => self.divide.points(attempt),
+        }
+    }
+}
+
+/// Another rule's points, kept in full for answers within ten seconds and
+/// halved for every further twenty, down to a quarter.
+pub struct SpeedWeighted {
+    pub base: Box<dyn ScoringRule>,
+}
+
+impl SpeedWeighted {
+    const FULL_POINTS_SECONDS: f64 = 10.0;
+    const HALF_LIFE_SECONDS: f64 = 20.0;
+
+    pub fn factor(seconds: f64) -> f64 {
+        let late = (seconds - Self::FULL_POINTS_SECONDS).max(0.0);
+        0.5_f64.powf(late / Self::HALF_LIFE_SECONDS).max(0.25)
+    }
+}
+
+impl ScoringRule for SpeedWeighted {
+    fn kind(&self) -> RuleKind {
+        RuleKind::SpeedWeighted
+    }
+
+    fn points(&self, attempt: &Attempt) -> u32 {
+        let points = f64::from(self.base.points(attempt)) * Self::factor(attempt.seconds);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let points = points.round() as u32;
+        points
+    }
+}
+
+/// The nominal coverage players aim for with interval answers
+pub const INTERVAL_COVERAGE: f64 = 0.8;
+
+/// The interval score of Gneiting and Raftery on log10 values: the width of a
+/// central `coverage` interval plus `2 / (1 - coverage)` times any miss, which
+/// is proper, so reporting honest bounds maximizes expected points. Points
+/// fall linearly with it, since any curve would break that. A hit 0.2 OOM
+/// wide earns 98, 1 OOM wide 90; each 0.1 OOM missed costs 10 more, down to 0
+/// a whole OOM out.
+pub struct IntervalScore {
+    pub coverage: f64,
+}
+
+impl IntervalScore {
+    const POINTS_PER_OOM: f64 = 10.0;
+
+    /// Lower is better; 0 is a zero-width interval on the truth
+    pub fn penalty(&self, attempt: &Attempt) -> f64 {
+        let (low, high) = (attempt.low.get().abs().log10(), attempt.high.get().abs().log10());
+        let (low, high) = (low.min(high), low.max(high));
+        let truth = attempt.correct.get().abs().log10();
+        let miss = (low - truth).max(0.0) + (truth - high).max(0.0);
+        (high - low) + 2.0 / (1.0 - self.coverage) * miss
+    }
+
+    /// Points before rounding. The floor at 0 is only reached by misses of
+    /// about a whole OOM, rare enough to leave the rule proper in practice.
+    fn exact_points(&self, attempt: &Attempt) -> f64 {
+        (100.0 - Self::POINTS_PER_OOM * self.penalty(attempt)).max(0.0)
+    }
+}
+
+impl ScoringRule for IntervalScore {
+    fn kind(&self) -> RuleKind {
+        RuleKind::IntervalScore
+    }
+
+    fn points(&self, attempt: &Attempt) -> u32 {
+        if attempt.low.is_negative() != attempt.correct.is_negative() {
+            return 0;
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let points = self.exact_points(attempt).round() as u32;
+        points
+    }
+}
+
+/// Share of interval answers that contained the truth, or `None` for none.
+/// Well calibrated 80% intervals land near 0.8.
+pub fn hit_rate<'a>(attempts: impl IntoIterator<Item = &'a Attempt>) -> Option<f64> {
+    let (hits, count) = attempts
+        .into_iter()
+        .filter(|a| a.is_interval())
+        .fold((0u32, 0u32), |(hits, count), a| (hits + u32::from(a.contains()), count + 1));
+    (count > 0).then(|| f64::from(hits) / f64::from(count))
+}
+
+/// Points decaying smoothly with log error: 100 when exact, about 98 at
+/// 0.1 OOM, 54 at 0.5 OOM and 8 at 1.0 OOM.
+pub fn continuous_points(distance: f64) -> u32 {
+    const WIDTH: f64 = 0.45;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let points = (100.0 * (-distance * distance / (2.0 * WIDTH * WIDTH)).exp()).round() as u32;
+    points
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn est(value: f64) -> Estimate {
+        Estimate::new(value).unwrap()
+    }
+
     #[test]
     fn test_oom_distance() {
-        assert!((oom_distance(1e6, 1e6) - 0.0).abs() < 0.001);
-        assert!((oom_distance(1e6, 1e7) - 1.0).abs() < 0.001);
-        assert!((oom_distance(1e6, 1e8) - 2.0).abs() < 0.001);
-        assert!((oom_distance(5e6, 1e6) - 0.699).abs() < 0.01);
+        assert!((oom_distance(est(1e6), est(1e6)) - 0.0).abs() < 0.001);
+        assert!((oom_distance(est(1e6), est(1e7)) - 1.0).abs() < 0.001);
+        assert!((oom_distance(est(1e6), est(1e8)) - 2.0).abs() < 0.001);
+        assert!((oom_distance(est(5e6), est(1e6)) - 0.699).abs() < 0.01);
     }
 
     #[test]
     fn test_evaluate() {
-        assert_eq!(evaluate(1e6, 1e6), ScoreResult::Exact);
-        assert_eq!(evaluate(2e6, 1e6), ScoreResult::Close);
-        assert_eq!(evaluate(5e6, 1e6), ScoreResult::Partial);
-        assert_eq!(evaluate(1e8, 1e6), ScoreResult::Wrong);
+        assert_eq!(evaluate(est(1e6), est(1e6)), ScoreResult::Exact);
+        assert_eq!(evaluate(est(2e6), est(1e6)), ScoreResult::Close);
+        assert_eq!(evaluate(est(5e6), est(1e6)), ScoreResult::Partial);
+        assert_eq!(evaluate(est(1e8), est(1e6)), ScoreResult::Wrong);
+    }
+
+    #[test]
+    fn test_estimate_validation() {
+        assert_eq!(Estimate::new(f64::NAN), Err(EstimateError::NotFinite));
+        assert_eq!(Estimate::new(f64::INFINITY), Err(EstimateError::NotFinite));
+        assert_eq!(Estimate::new(0.0), Err(EstimateError::Zero));
+        assert_eq!(est(-5.0).get(), -5.0);
+        assert!((est(1e3).geometric_mean(est(1e5)).get() - 1e4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_evaluate_signed() {
+        assert!((oom_distance(est(-1e6), est(1e7)) - 1.0).abs() < 0.001);
+        assert_eq!(evaluate(est(-1e6), est(-1e6)), ScoreResult::Exact);
+        assert_eq!(evaluate(est(-5e6), est(-1e6)), ScoreResult::Partial);
+        assert_eq!(evaluate(est(-1e6), est(1e6)), ScoreResult::WrongSign);
+        assert_eq!(evaluate(est(2e6), est(-1e6)), ScoreResult::WrongSign);
+        assert_eq!(evaluate(est(5e6), est(-1e6)), ScoreResult::Wrong);
+        assert!((est(-1e3).geometric_mean(est(-1e5)).get() + 1e4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_continuous_points() {
+        assert_eq!(continuous_points(0.0), 100);
+        assert_eq!(continuous_points(0.1), 98);
+        assert_eq!(continuous_points(0.5), 54);
+        assert_eq!(continuous_points(1.0), 8);
+        assert!(continuous_points(0.49) - continuous_points(0.51) <= 2);
+        assert_eq!(continuous_points(5.0), 0);
+    }
+
+    fn attempt(low: f64, high: f64, correct: f64) -> Attempt {
+        Attempt { low: est(low), high: est(high), correct: est(correct), operation: Operation::Multiply, seconds: 5.0 }
+    }
+
+    fn points(kind: RuleKind, attempt: &Attempt) -> u32 {
+        kind.rule().score(attempt).points
+    }
+
+    #[test]
+    fn test_rules() {
+        let close = attempt(2e6, 2e6, 1e6);
+        let banded = RuleKind::Banded.rule().score(&close);
+        assert_eq!((banded.result, banded.points, banded.rule), (ScoreResult::Close, 75, RuleKind::Banded));
+        assert!((banded.log_error - 2_f64.log10()).abs() < 1e-9);
+        assert_eq!(points(RuleKind::Continuous, &close), 80);
+        assert_eq!(points(RuleKind::Continuous, &attempt(-2e6, -2e6, 1e6)), 26);
+        assert_eq!(points(RuleKind::Strict, &close), 0);
+        assert_eq!(points(RuleKind::Strict, &attempt(1.1e6, 1.1e6, 1e6)), 100);
+        for kind in RuleKind::ALL {
+            assert_eq!(kind.rule().kind(), kind);
+        }
+    }
+
+    #[test]
+    fn test_interval_log() {
+        assert_eq!(points(RuleKind::IntervalLog, &attempt(1e6, 1e6, 1e6)), 100);
+        assert_eq!(points(RuleKind::IntervalLog, &attempt(2e6, 2e6, 1e6)), 0);
+        assert_eq!(points(RuleKind::IntervalLog, &attempt(5e5, 5e6, 1e6)), 50);
+        assert_eq!(points(RuleKind::IntervalLog, &attempt(2e6, 2e7, 1e6)), 0);
+        assert_eq!(points(RuleKind::IntervalLog, &attempt(1e2, 1e12, 1e6)), 0);
+        assert_eq!(points(RuleKind::IntervalLog, &attempt(-5e6, -5e5, 1e6)), 0);
+    }
+
+    #[test]
+    fn test_per_operation() {
+        let close = attempt(2e6, 2e6, 1e6);
+        let division = Attempt { operation: Operation::Divide, ..close };
+        assert_eq!(points(RuleKind::PerOperation, &close), 0);
+        assert_eq!(points(RuleKind::PerOperation, &division), 75);
+    }
+
+    #[test]
+    fn test_log_error_and_bias() {
+        assert!((log_error(est(1e5), est(1e6)) + 1.0).abs() < 1e-9);
+        assert!((log_error(est(-1e7), est(1e6)) - 1.0).abs() < 1e-9);
+        assert_eq!(mean_bias([]), None);
+        let bias = mean_bias([-0.5, -0.1, 0.3]).unwrap();
+        assert!((bias + 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_components() {
+        let zeros = Components::new(est(4e12), est(4e11));
+        assert_eq!(zeros.exponent_error, 1);
+        assert!(zeros.mantissa_error.abs() < 1e-9);
+        assert!(!zeros.exponent_correct());
+
+        let mantissa = Components::new(est(6e11), est(4e11));
+        assert!(mantissa.exponent_correct());
+        assert!((mantissa.mantissa_percent() - 50.0).abs() < 1e-6);
+
+        let boundary = Components::new(est(9.8e5), est(1.02e6));
+        assert!(boundary.exponent_correct());
+        assert!(boundary.mantissa_percent() < 0.0);
+
+        let both = Components::new(est(2e3), est(8e5));
+        assert_eq!(both.exponent_error, -3);
+        assert!((both.mantissa_percent() - 150.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_interval_score() {
+        let rule = IntervalScore { coverage: INTERVAL_COVERAGE };
+        assert_eq!(rule.points(&attempt(1e6, 1e6, 1e6)), 100);
+        assert_eq!(rule.points(&attempt(10_f64.powf(5.9), 10_f64.powf(6.1), 1e6)), 98);
+        assert_eq!(rule.points(&attempt(10_f64.powf(5.5), 10_f64.powf(6.5), 1e6)), 90);
+        assert_eq!(rule.points(&attempt(10_f64.powf(6.5), 10_f64.powf(6.7), 1e6)), 48);
+        assert_eq!(rule.points(&attempt(10_f64.powf(7.5), 10_f64.powf(7.7), 1e6)), 0);
+        assert_eq!(rule.points(&attempt(-2e6, -5e5, 1e6)), 0);
+        // Reversed bounds score the same
+        assert_eq!(rule.points(&attempt(5e6, 5e5, 1e6)), rule.points(&attempt(5e5, 5e6, 1e6)));
+    }
+
+    #[test]
+    fn test_interval_score_rewards_honest_coverage() {
+        // A player whose log error is normal with spread `sigma`, answering
+        // symmetric intervals: the width with the best expected points should
+        // cover the truth 80% of the time
+        let rule = IntervalScore { coverage: INTERVAL_COVERAGE };
+        for sigma in [0.1, 0.3, 0.5] {
+            let step = sigma / 200.0;
+            let offsets: Vec<(f64, f64)> = (-1600..=1600)
+                .map(|i| {
+                    let t = f64::from(i) * step;
+                    (t, (-t * t / (2.0 * sigma * sigma)).exp())
+                })
+                .collect();
+            let total: f64 = offsets.iter().map(|(_, w)| w).sum();
+            let expected_points = |half: f64| {
+                let answer = attempt(10_f64.powf(6.0 - half), 10_f64.powf(6.0 + half), 1e6);
+                offsets
+                    .iter()
+                    .map(|&(t, w)| {
+                        let truth = Estimate::new(10_f64.powf(6.0 + t)).unwrap();
+                        w * rule.exact_points(&Attempt { correct: truth, ..answer })
+                    })
+                    .sum::<f64>()
+                    / total
+            };
+            let best = (1..=300)
+                .map(|i| f64::from(i) * sigma / 100.0)
+                .max_by(|&a, &b| expected_points(a).total_cmp(&expected_points(b)))
+                .unwrap();
+            let coverage = offsets.iter().filter(|(t, _)| t.abs() <= best).map(|(_, w)| w).sum::<f64>() / total;
+            assert!((coverage - INTERVAL_COVERAGE).abs() < 0.02, "sigma {sigma}: best covers {coverage:.3}");
+        }
+    }
+
+    #[test]
+    fn test_hit_rate() {
+        assert_eq!(hit_rate(&[attempt(1e6, 1e6, 1e6)]), None);
+        let attempts = [
+            attempt(5e5, 5e6, 1e6),
+            attempt(2e6, 5e6, 1e6),
+            attempt(-5e6, -5e5, -1e6),
+            attempt(1e6, 1e6, 1e6),
+        ];
+        assert_eq!(hit_rate(&attempts), Some(2.0 / 3.0));
+    }
+
+    #[test]
+    fn test_speed_weighted() {
+        assert!((SpeedWeighted::factor(3.0) - 1.0).abs() < 1e-9);
+        assert!((SpeedWeighted::factor(30.0) - 0.5).abs() < 1e-9);
+        assert!((SpeedWeighted::factor(600.0) - 0.25).abs() < 1e-9);
+        let close = attempt(2e6, 2e6, 1e6);
+        assert_eq!(points(RuleKind::SpeedWeighted, &close), 75);
+        assert_eq!(points(RuleKind::SpeedWeighted, &Attempt { seconds: 30.0, ..close }), 38);
     }
 }
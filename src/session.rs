@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::Challenge;
+use crate::history::{ProblemRecord, SessionRecord};
+use crate::parser::Answer;
+use crate::scoring::{Attempt, Estimate, RuleKind, Score};
+
+/// One answered problem of the daily session.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProblemResult {
+    pub challenge: Challenge,
+    pub answer: Answer,
+    pub score: Score,
+    pub seconds: f64,
+}
+
+pub fn attempt(challenge: Challenge, answer: Answer, seconds: f64) -> Option<Attempt> {
+    let (low, high) = answer.bounds();
+    let correct = Estimate::new(challenge.answer()).ok()?;
+    Some(Attempt { low, high, correct, operation: challenge.operation(), seconds })
+}
+
+/// The in-progress daily session, saved after every change so a reload
+/// resumes it. `index` is the problem on screen; a result at `index` means it
+/// was submitted and its result card is showing.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub seed: u64,
+    pub index: usize,
+    pub results: Vec<ProblemResult>,
+    /// When problem `index` was first shown, in ms since the epoch
+    #[serde(default)]
+    pub started: Option<f64>,
+    /// The day's scoring rule, fixed once the first answer is in
+    #[serde(default)]
+    pub rule: RuleKind,
+    /// Whether today's problems include negative operands
+    #[serde(default)]
+    pub signed: bool,
+}
+
+impl Session {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, index: 0, results: Vec::new(), started: None, rule: RuleKind::default(), signed: false }
+    }
+
+    /// Resume `saved` if it belongs to today's seed, otherwise start fresh.
+    /// Saved data that doesn't hang together is discarded too, unless today
+    /// is already `recorded` in the history: then the day stays finished, so
+    /// it can't be replayed. Settings carry over to a fresh day either way.
+    pub fn resume(saved: Option<Self>, recorded: Option<&SessionRecord>, seed: u64, problems: usize) -> Self {
+        let (rule, signed) = saved.as_ref().map_or((RuleKind::default(), false), |s| (s.rule, s.signed));
+        saved
+            .filter(|s| s.seed == seed)
+            .filter(|s| s.index <= problems && s.results.len() <= problems)
+            .filter(|s| s.results.len() == s.index || s.results.len() == s.index + 1)
+            .or_else(|| {
+                let recorded = recorded.filter(|r| r.seed == seed)?;
+                let results = recorded.problems.iter().copied().filter_map(ProblemRecord::to_result).collect();
+                let rule = recorded.problems.first().map_or(rule, |p| p.rule);
+                Some(Self { index: problems, results, rule, signed, ..Self::new(seed) })
+            })
+            .unwrap_or_else(|| Self { rule, signed, ..Self::new(seed) })
+    }
+
+    pub fn is_submitted(&self) -> bool {
+        self.results.len() > self.index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::RuleKind;
+
+    fn result() -> ProblemResult {
+        let challenge = Challenge { num1: 2e6, num2: 3e3, is_division: false };
+        let answer = Answer::Point(Estimate::new(5e9).unwrap());
+        let score = RuleKind::Banded.rule().score(&attempt(challenge, answer, 4.0).unwrap());
+        ProblemResult { challenge, answer, score, seconds: 4.0 }
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let session = Session {
+            seed: 42,
+            index: 1,
+            results: vec![result(), result()],
+            started: Some(1_760_000_000_000.0),
+            rule: RuleKind::SpeedWeighted,
+            signed: true,
+        };
+        let json = serde_json::to_string(&session).unwrap();
+        assert_eq!(serde_json::from_str::<Session>(&json).unwrap(), session);
+    }
+
+    #[test]
+    fn test_invalid_estimate_is_rejected() {
+        let json = serde_json::to_string(&result()).unwrap().replace("5000000000.0", "0.0");
+        assert!(serde_json::from_str::<ProblemResult>(&json).is_err());
+    }
+
+    #[test]
+    fn test_resume() {
+        // The clock on the open problem survives the reload
+        let started = Some(1_760_000_000_000.0);
+        let saved = Session { index: 1, results: vec![result()], started, ..Session::new(42) };
+        assert_eq!(Session::resume(Some(saved.clone()), None, 42, 5), saved);
+        assert_eq!(Session::resume(Some(saved.clone()), None, 7, 5), Session::new(7));
+        assert_eq!(Session::resume(None, None, 42, 5), Session::new(42));
+        let inconsistent = Session { index: 3, results: vec![result()], ..Session::new(42) };
+        assert_eq!(Session::resume(Some(inconsistent), None, 42, 5), Session::new(42));
+    }
+
+    #[test]
+    fn test_settings_carry_over() {
+        let yesterday = Session { index: 5, rule: RuleKind::Strict, signed: true, ..Session::new(41) };
+        let today = Session { rule: RuleKind::Strict, signed: true, ..Session::new(42) };
+        assert_eq!(Session::resume(Some(yesterday), None, 42, 5), today);
+    }
+
+    #[test]
+    fn test_recorded_day_stays_finished() {
+        let recorded = SessionRecord { date: "2026-10-18".into(), seed: 42, problems: vec![(&result()).into(); 5] };
+        // Lost or unreadable progress can't reopen a finished day
+        for saved in [None, Some(Session { index: 9, ..Session::new(42) })] {
+            let session = Session::resume(saved, Some(&recorded), 42, 5);
+            assert_eq!(session.index, 5);
+            assert_eq!(session.results, vec![result(); 5]);
+        }
+        // Another day's record doesn't count
+        assert_eq!(Session::resume(None, Some(&recorded), 43, 5), Session::new(43));
+    }
+
+    #[test]
+    fn test_is_submitted() {
+        assert!(!Session { index: 1, results: vec![result()], ..Session::new(1) }.is_submitted());
+        assert!(Session { results: vec![result()], ..Session::new(1) }.is_submitted());
+    }
+}
@@ -0,0 +1,239 @@
+use crate::challenge::{Challenge, Operation};
+use crate::history::{History, ProblemRecord};
+use crate::scoring::{mean_bias, ScoreResult};
+
+/// Sessions averaged by the rolling error line
+pub const ROLLING_WINDOW: usize = 7;
+
+/// What made a problem harder than a plain one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Skill {
+    /// The mantissa step crosses a power of ten
+    Carry,
+    /// At least one operand is negative
+    Negative,
+}
+
+impl Skill {
+    pub const ALL: [Self; 2] = [Self::Carry, Self::Negative];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Carry => "Carry",
+            Self::Negative => "Negative",
+        }
+    }
+
+    pub fn applies(self, challenge: &Challenge) -> bool {
+        match self {
+            Self::Carry => challenge.carries(),
+            Self::Negative => challenge.num1 < 0.0 || challenge.num2 < 0.0,
+        }
+    }
+}
+
+/// One completed session, reduced to the numbers the charts plot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayStats {
+    pub date: String,
+    pub score: u32,
+    /// Mean distance from the truth, in OOM
+    pub mean_distance: f64,
+    /// Mean signed error in OOM; negative means too low
+    pub bias: f64,
+    pub mean_seconds: f64,
+}
+
+/// One point per recorded session, oldest first. Empty sessions are skipped.
+pub fn daily(history: &History) -> Vec<DayStats> {
+    history
+        .sessions
+        .iter()
+        .filter_map(|session| {
+            let problems = &session.problems;
+            let bias = mean_bias(problems.iter().map(|p| p.log_error))?;
+            Some(DayStats {
+                date: session.date.clone(),
+                score: problems.iter().map(|p| p.points).sum(),
+                mean_distance: mean(problems.iter().map(|p| p.distance)),
+                bias,
+                mean_seconds: mean(problems.iter().map(|p| p.seconds)),
+            })
+        })
+        .collect()
+}
+
+fn mean(values: impl ExactSizeIterator<Item = f64>) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let count = values.len().max(1) as f64;
+    values.sum::<f64>() / count
+}
+
+/// The mean of each value and up to `window - 1` before it, so the line
+/// starts at the first day rather than `window` days in.
+pub fn rolling_mean(values: &[f64], window: usize) -> Vec<f64> {
+    (0..values.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(window.max(1));
+            mean(values[start..=i].iter().copied())
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Accuracy {
+    /// Answers within 0.5 OOM with the right sign
+    pub hits: u32,
+    pub total: u32,
+}
+
+impl Accuracy {
+    fn of<'a>(problems: impl Iterator<Item = &'a ProblemRecord>) -> Self {
+        problems.fold(Self::default(), |acc, p| Self {
+            hits: acc.hits + u32::from(matches!(p.result, ScoreResult::Exact | ScoreResult::Close)),
+            total: acc.total + 1,
+        })
+    }
+
+    /// `None` with nothing answered yet
+    pub fn fraction(self) -> Option<f64> {
+        (self.total > 0).then(|| f64::from(self.hits) / f64::from(self.total))
+    }
+}
+
+fn problems(history: &History) -> impl Iterator<Item = &ProblemRecord> {
+    history.sessions.iter().flat_map(|s| &s.problems)
+}
+
+/// Every recorded answer except the session for `date` and `seed`, oldest
+/// first. Today's answers come from the live session instead, since they
+/// reach the history only once the day is complete.
+pub fn past_problems(history: &History, date: &str, seed: u64) -> Vec<ProblemRecord> {
+    history
+        .sessions
+        .iter()
+        .filter(|s| !(s.date == date && s.seed == seed))
+        .flat_map(|s| s.problems.iter().copied())
+        .collect()
+}
+
+/// Mean signed error on `operation` and how many answers it rests on.
+pub fn bias_by_operation(problems: &[ProblemRecord], operation: Operation) -> Option<(f64, usize)> {
+    let errors: Vec<f64> =
+        problems.iter().filter(|p| p.challenge.operation() == operation).map(|p| p.log_error).collect();
+    mean_bias(errors.iter().copied()).map(|bias| (bias, errors.len()))
+}
+
+pub fn accuracy_by_operation(history: &History, operation: Operation) -> Accuracy {
+    Accuracy::of(problems(history).filter(|p| p.challenge.operation() == operation))
+}
+
+pub fn accuracy_by_skill(history: &History, skill: Skill) -> Accuracy {
+    Accuracy::of(problems(history).filter(|p| skill.applies(&p.challenge)))
+}
+
+/// The y-axis range for a chart of `values`, padded so points don't sit on
+/// the edges and never zero-height. `baseline` is kept in view when given.
+pub fn value_range(values: &[f64], baseline: Option<f64>) -> (f64, f64) {
+    let all = || values.iter().copied().chain(baseline);
+    let (low, high) = (all().fold(f64::INFINITY, f64::min), all().fold(f64::NEG_INFINITY, f64::max));
+    if !low.is_finite() || !high.is_finite() {
+        return (0.0, 1.0);
+    }
+    let pad = ((high - low) * 0.1).max(high.abs().max(1.0) * 0.05);
+    (low - pad, high + pad)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::SessionRecord;
+    use crate::parser::Answer;
+    use crate::scoring::{Estimate, RuleKind};
+
+    fn problem(challenge: Challenge, log_error: f64, seconds: f64) -> ProblemRecord {
+        let result = if log_error.abs() <= 0.5 { ScoreResult::Close } else { ScoreResult::Wrong };
+        ProblemRecord {
+            challenge,
+            answer: Answer::Point(Estimate::new(challenge.answer() * 10_f64.powf(log_error)).unwrap()),
+            result,
+            points: result.points(),
+            rule: RuleKind::Banded,
+            log_error,
+            distance: log_error.abs(),
+            seconds,
+        }
+    }
+
+    fn history() -> History {
+        let multiply = Challenge { num1: 2e6, num2: 3e3, is_division: false };
+        let carry = Challenge { num1: 4e6, num2: 5e3, is_division: false };
+        let negative = Challenge { num1: -8e9, num2: 2e3, is_division: true };
+        History {
+            sessions: vec![
+                SessionRecord {
+                    date: "2026-10-17".into(),
+                    seed: 1,
+                    problems: vec![problem(multiply, 0.2, 10.0), problem(carry, -1.0, 20.0)],
+                },
+                SessionRecord { date: "2026-10-18".into(), seed: 2, problems: vec![] },
+                SessionRecord {
+                    date: "2026-10-19".into(),
+                    seed: 3,
+                    problems: vec![problem(negative, -0.4, 6.0), problem(carry, 0.1, 4.0)],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_daily() {
+        let days = daily(&history());
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "2026-10-17");
+        assert_eq!(days[0].score, 75);
+        assert!((days[0].mean_distance - 0.6).abs() < 1e-9);
+        assert!((days[0].bias + 0.4).abs() < 1e-9);
+        assert!((days[0].mean_seconds - 15.0).abs() < 1e-9);
+        assert_eq!(days[1].score, 150);
+    }
+
+    #[test]
+    fn test_rolling_mean() {
+        assert_eq!(rolling_mean(&[2.0, 4.0, 6.0, 8.0], 2), vec![2.0, 3.0, 5.0, 7.0]);
+        assert_eq!(rolling_mean(&[2.0, 4.0], 7), vec![2.0, 3.0]);
+        assert!(rolling_mean(&[], 7).is_empty());
+    }
+
+    #[test]
+    fn test_accuracy() {
+        let history = history();
+        assert_eq!(accuracy_by_operation(&history, Operation::Multiply), Accuracy { hits: 2, total: 3 });
+        assert_eq!(accuracy_by_operation(&history, Operation::Divide), Accuracy { hits: 1, total: 1 });
+        assert_eq!(accuracy_by_skill(&history, Skill::Carry), Accuracy { hits: 1, total: 2 });
+        assert_eq!(accuracy_by_skill(&history, Skill::Negative).fraction(), Some(1.0));
+        assert_eq!(Accuracy::default().fraction(), None);
+    }
+
+    #[test]
+    fn test_past_problems_and_bias() {
+        let past = past_problems(&history(), "2026-10-19", 3);
+        assert_eq!(past.len(), 2);
+        let (bias, count) = bias_by_operation(&past, Operation::Multiply).unwrap();
+        assert!((bias + 0.4).abs() < 1e-9);
+        assert_eq!(count, 2);
+        assert_eq!(bias_by_operation(&past, Operation::Divide), None);
+        assert_eq!(past_problems(&history(), "2026-10-19", 4).len(), 4);
+    }
+
+    #[test]
+    fn test_value_range() {
+        let (low, high) = value_range(&[100.0, 300.0], None);
+        assert!(low < 100.0 && high > 300.0);
+        let (low, high) = value_range(&[0.2, 0.4], Some(0.0));
+        assert!(low < 0.0 && high > 0.4);
+        let (low, high) = value_range(&[5.0], None);
+        assert!(low < 5.0 && high > 5.0);
+        assert_eq!(value_range(&[], None), (0.0, 1.0));
+    }
+}
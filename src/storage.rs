@@ -0,0 +1,160 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::JsCast;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageError {
+    /// Storage is disabled, e.g. in private browsing or by site settings
+    Unavailable,
+    QuotaExceeded,
+    /// The value couldn't be encoded
+    Encoding,
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unavailable => "storage is disabled",
+            Self::QuotaExceeded => "storage is full",
+            Self::Encoding => "couldn't encode the data",
+        })
+    }
+}
+
+/// A string key-value store that persists progress between visits.
+pub trait StorageBackend {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError>;
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError>;
+    /// False if data is lost when the page closes
+    fn is_persistent(&self) -> bool;
+}
+
+/// The browser's localStorage.
+pub struct LocalStorage {
+    storage: web_sys::Storage,
+}
+
+impl LocalStorage {
+    /// `None` when storage is disabled; some browsers throw just for asking.
+    pub fn new() -> Option<Self> {
+        let storage = web_sys::window()?.local_storage().ok()??;
+        Some(Self { storage })
+    }
+}
+
+impl StorageBackend for LocalStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        self.storage.get_item(key).map_err(|_| StorageError::Unavailable)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.storage.set_item(key, value).map_err(|err| {
+            // Browsers disagree on the code but agree on the name
+            let quota = err
+                .dyn_ref::<web_sys::DomException>()
+                .is_some_and(|e| matches!(e.name().as_str(), "QuotaExceededError" | "NS_ERROR_DOM_QUOTA_REACHED"));
+            if quota { StorageError::QuotaExceeded } else { StorageError::Unavailable }
+        })
+    }
+
+    fn is_persistent(&self) -> bool {
+        true
+    }
+}
+
+/// Keeps everything in memory: for native tests, and as the fallback when the
+/// browser won't store anything, so a session still works until the tab closes.
+#[derive(Default)]
+pub struct MemoryStorage {
+    items: RefCell<HashMap<String, String>>,
+    /// Total bytes of keys and values allowed, to exercise quota handling
+    quota: Option<usize>,
+}
+
+impl MemoryStorage {
+    #[cfg(test)]
+    pub fn with_quota(bytes: usize) -> Self {
+        Self { quota: Some(bytes), ..Self::default() }
+    }
+}
+
+impl StorageBackend for MemoryStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        Ok(self.items.borrow().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), StorageError> {
+        let mut items = self.items.borrow_mut();
+        if let Some(quota) = self.quota {
+            let others: usize = items.iter().filter(|(k, _)| *k != key).map(|(k, v)| k.len() + v.len()).sum();
+            if others + key.len() + value.len() > quota {
+                return Err(StorageError::QuotaExceeded);
+            }
+        }
+        items.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn is_persistent(&self) -> bool {
+        false
+    }
+}
+
+/// localStorage if the browser allows it, memory otherwise.
+pub fn browser_storage() -> Rc<dyn StorageBackend> {
+    match LocalStorage::new() {
+        Some(storage) => Rc::new(storage),
+        None => Rc::new(MemoryStorage::default()),
+    }
+}
+
+/// Missing or unreadable data reads as `None`, so callers start over.
+pub fn load<T: DeserializeOwned>(storage: &dyn StorageBackend, key: &str) -> Option<T> {
+    let json = storage.get(key).ok()??;
+    serde_json::from_str(&json).ok()
+}
+
+pub fn save<T: Serialize>(storage: &dyn StorageBackend, key: &str, value: &T) -> Result<(), StorageError> {
+    let json = serde_json::to_string(value).map_err(|_| StorageError::Encoding)?;
+    storage.set(key, &json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_storage() {
+        let storage = MemoryStorage::default();
+        assert_eq!(storage.get("a"), Ok(None));
+        storage.set("a", "1").unwrap();
+        storage.set("a", "2").unwrap();
+        assert_eq!(storage.get("a"), Ok(Some("2".into())));
+        assert!(!storage.is_persistent());
+    }
+
+    #[test]
+    fn test_quota() {
+        let storage = MemoryStorage::with_quota(10);
+        storage.set("a", "12345").unwrap();
+        assert_eq!(storage.set("b", "123456"), Err(StorageError::QuotaExceeded));
+        // Replacing a value only counts the new one
+        storage.set("a", "123456789").unwrap();
+        assert_eq!(storage.get("b"), Ok(None));
+    }
+
+    #[test]
+    fn test_typed_load_and_save() {
+        let storage = MemoryStorage::default();
+        save(&storage, "numbers", &vec![1, 2, 3]).unwrap();
+        assert_eq!(load::<Vec<i32>>(&storage, "numbers"), Some(vec![1, 2, 3]));
+        assert_eq!(load::<Vec<i32>>(&storage, "missing"), None);
+        storage.set("garbled", "{").unwrap();
+        assert_eq!(load::<Vec<i32>>(&storage, "garbled"), None);
+    }
+}